@@ -0,0 +1,264 @@
+//! Parse error types and the structured diagnostics the parser reports.
+//!
+//! This file only declares the subset of the upstream `ruff_python_parser::error`
+//! module that `parser/mod.rs` in this tree actually depends on. It is not a full
+//! reconstruction of that module: `LexicalErrorType` still comes from `crate::lexer`,
+//! which -- like the rest of the crate outside `parser/mod.rs` (`lib.rs`, `lexer.rs`,
+//! `token_set.rs`, `string.rs`, ...) -- isn't part of this snapshot and predates this
+//! series, so it isn't declared here either.
+
+use ruff_text_size::TextRange;
+
+use crate::lexer::LexicalErrorType;
+use crate::parser::{Applicability, Suggestion};
+use crate::TokenKind;
+
+/// A single parse error: what went wrong, where, and any [`Suggestion`]s a caller
+/// (an editor, the formatter, `ruff`'s own fixer) could apply to fix it.
+#[derive(Debug, Clone)]
+pub struct ParseError {
+    pub error: ParseErrorType,
+    pub location: TextRange,
+    pub suggestions: Vec<Suggestion>,
+}
+
+impl std::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.error)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// The kind of error encountered while parsing.
+#[derive(Debug, Clone)]
+pub enum ParseErrorType {
+    /// A generic error with a custom message.
+    OtherError(String),
+    /// The parser expected a specific token but found a different one.
+    ExpectedToken {
+        found: TokenKind,
+        expected: TokenKind,
+    },
+    /// A match pattern literal that isn't one of the few literal kinds a pattern
+    /// can match against (a number, string, `None`/`True`/`False`, ...).
+    InvalidMatchPatternLiteral { pattern: TokenKind },
+    /// A sequence pattern with the wrong delimiter: either a `[`/`(` closed by
+    /// something other than its matching `]`/`)` (`expected` is the matching
+    /// closer, `found` the one actually used), or a `{...}` written as a sequence
+    /// pattern (`expected` is `[`, `found` is `{`).
+    MismatchedMatchPatternDelimiter {
+        expected: TokenKind,
+        found: TokenKind,
+    },
+    /// An `async` statement that isn't one of `def`, `for`, or `with`.
+    StmtIsNotAsync(TokenKind),
+    AssignmentError,
+    AugAssignmentError,
+    NamedAssignmentError,
+    SimpleStmtsInSameLine,
+    SimpleStmtAndCompoundStmtInSameLine,
+    UnpackedArgumentError,
+    PositionalArgumentError,
+    DefaultArgumentError,
+    ParamFollowsVarKeywordParam,
+    EmptySlice,
+    /// Mutual recursion between pattern, expression, and nested statement-body
+    /// parsing exceeded [`crate::parser::Parser::MAX_RECURSION_DEPTH`].
+    RecursionLimitExceeded,
+    Lexical(LexicalErrorType),
+    FStringError(FStringErrorType),
+}
+
+impl std::fmt::Display for ParseErrorType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseErrorType::OtherError(msg) => write!(f, "{msg}"),
+            ParseErrorType::ExpectedToken { found, expected } => {
+                write!(
+                    f,
+                    "expected {}, found {}",
+                    TokenDescription(expected),
+                    TokenDescription(found)
+                )
+            }
+            ParseErrorType::InvalidMatchPatternLiteral { pattern } => {
+                write!(f, "invalid match pattern literal: {}", TokenDescription(pattern))
+            }
+            ParseErrorType::MismatchedMatchPatternDelimiter { expected, found } => {
+                write!(
+                    f,
+                    "mismatched delimiter in match pattern: expected {}, found {}",
+                    TokenDescription(expected),
+                    TokenDescription(found)
+                )
+            }
+            ParseErrorType::StmtIsNotAsync(kind) => {
+                write!(
+                    f,
+                    "expected `def`, `for`, or `with` after `async`, found {}",
+                    TokenDescription(kind)
+                )
+            }
+            ParseErrorType::AssignmentError => write!(f, "invalid assignment target"),
+            ParseErrorType::AugAssignmentError => write!(f, "invalid augmented assignment target"),
+            ParseErrorType::NamedAssignmentError => write!(f, "invalid named assignment target"),
+            ParseErrorType::SimpleStmtsInSameLine => {
+                write!(f, "simple statements on the same line must be separated by `;`")
+            }
+            ParseErrorType::SimpleStmtAndCompoundStmtInSameLine => write!(
+                f,
+                "compound statements are not allowed on the same line as simple statements"
+            ),
+            ParseErrorType::UnpackedArgumentError => write!(f, "invalid unpacked argument"),
+            ParseErrorType::PositionalArgumentError => {
+                write!(f, "positional argument follows keyword argument")
+            }
+            ParseErrorType::DefaultArgumentError => {
+                write!(f, "parameter without a default follows a parameter with a default")
+            }
+            ParseErrorType::ParamFollowsVarKeywordParam => {
+                write!(f, "parameter follows `**kwargs`")
+            }
+            ParseErrorType::EmptySlice => write!(f, "expected index or slice expression"),
+            ParseErrorType::RecursionLimitExceeded => write!(f, "parsing recursed too deeply"),
+            ParseErrorType::Lexical(error) => write!(f, "{error}"),
+            ParseErrorType::FStringError(error) => write!(f, "f-string: {error}"),
+        }
+    }
+}
+
+/// Renders a [`TokenKind`] the way a reader would describe it, rather than its
+/// internal `Debug` spelling: operators and punctuation as the literal text
+/// (`` `)` ``, not `Rpar`), except the augmented-assignment operators (`+=`,
+/// `-=`, ...), which keep their `Debug` spelling; keywords as
+/// `` keyword `yield` `` (not `Yield`); and
+/// everything else (identifiers, literals, layout tokens) as a short category
+/// name ("a string literal", "end of file"), so diagnostics read like something
+/// a human wrote the source with.
+struct TokenDescription<'a>(&'a TokenKind);
+
+impl std::fmt::Display for TokenDescription<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let literal = match self.0 {
+            TokenKind::Lpar => "`(`",
+            TokenKind::Rpar => "`)`",
+            TokenKind::Lsqb => "`[`",
+            TokenKind::Rsqb => "`]`",
+            TokenKind::Lbrace => "`{`",
+            TokenKind::Rbrace => "`}`",
+            TokenKind::Comma => "`,`",
+            TokenKind::Colon => "`:`",
+            TokenKind::Semi => "`;`",
+            TokenKind::Dot => "`.`",
+            TokenKind::Ellipsis => "`...`",
+            TokenKind::At => "`@`",
+            TokenKind::Equal => "`=`",
+            TokenKind::ColonEqual => "`:=`",
+            TokenKind::EqEqual => "`==`",
+            TokenKind::NotEqual => "`!=`",
+            TokenKind::Less => "`<`",
+            TokenKind::LessEqual => "`<=`",
+            TokenKind::Greater => "`>`",
+            TokenKind::GreaterEqual => "`>=`",
+            TokenKind::Rarrow => "`->`",
+            TokenKind::Plus => "`+`",
+            TokenKind::Minus => "`-`",
+            TokenKind::Star => "`*`",
+            TokenKind::DoubleStar => "`**`",
+            TokenKind::Slash => "`/`",
+            TokenKind::DoubleSlash => "`//`",
+            TokenKind::Percent => "`%`",
+            TokenKind::Amper => "`&`",
+            TokenKind::Vbar => "`|`",
+            TokenKind::CircumFlex => "`^`",
+            TokenKind::Tilde => "`~`",
+            TokenKind::LeftShift => "`<<`",
+            TokenKind::RightShift => "`>>`",
+            TokenKind::Question => "`?`",
+            TokenKind::Exclamation => "`!`",
+
+            TokenKind::False => "keyword `False`",
+            TokenKind::None => "keyword `None`",
+            TokenKind::True => "keyword `True`",
+            TokenKind::And => "keyword `and`",
+            TokenKind::As => "keyword `as`",
+            TokenKind::Assert => "keyword `assert`",
+            TokenKind::Async => "keyword `async`",
+            TokenKind::Await => "keyword `await`",
+            TokenKind::Break => "keyword `break`",
+            TokenKind::Case => "keyword `case`",
+            TokenKind::Class => "keyword `class`",
+            TokenKind::Continue => "keyword `continue`",
+            TokenKind::Def => "keyword `def`",
+            TokenKind::Del => "keyword `del`",
+            TokenKind::Elif => "keyword `elif`",
+            TokenKind::Else => "keyword `else`",
+            TokenKind::Except => "keyword `except`",
+            TokenKind::Finally => "keyword `finally`",
+            TokenKind::For => "keyword `for`",
+            TokenKind::From => "keyword `from`",
+            TokenKind::Global => "keyword `global`",
+            TokenKind::If => "keyword `if`",
+            TokenKind::Import => "keyword `import`",
+            TokenKind::In => "keyword `in`",
+            TokenKind::Is => "keyword `is`",
+            TokenKind::Lambda => "keyword `lambda`",
+            TokenKind::Match => "keyword `match`",
+            TokenKind::Nonlocal => "keyword `nonlocal`",
+            TokenKind::Not => "keyword `not`",
+            TokenKind::Or => "keyword `or`",
+            TokenKind::Pass => "keyword `pass`",
+            TokenKind::Raise => "keyword `raise`",
+            TokenKind::Return => "keyword `return`",
+            TokenKind::Try => "keyword `try`",
+            TokenKind::Type => "keyword `type`",
+            TokenKind::While => "keyword `while`",
+            TokenKind::With => "keyword `with`",
+            TokenKind::Yield => "keyword `yield`",
+
+            TokenKind::Name => "an identifier",
+            TokenKind::Int => "an integer literal",
+            TokenKind::Float => "a float literal",
+            TokenKind::Complex => "a complex literal",
+            TokenKind::String => "a string literal",
+            TokenKind::FStringStart => "the start of an f-string",
+            TokenKind::FStringMiddle => "f-string text",
+            TokenKind::FStringEnd => "the end of an f-string",
+            TokenKind::Newline => "a newline",
+            TokenKind::Indent => "an indent",
+            TokenKind::Dedent => "a dedent",
+            TokenKind::EndOfFile => "end of file",
+            TokenKind::EscapeCommand => "an IPython escape command",
+            TokenKind::Unknown => return write!(f, "an unknown token"),
+            // Augmented-assignment operators (`+=`, `-=`, ...) and any other token
+            // this list hasn't been taught about yet: fall back to its `Debug`
+            // spelling rather than failing to compile against future variants.
+            other => return write!(f, "{other:?}"),
+        };
+        write!(f, "{literal}")
+    }
+}
+
+/// The kind of error encountered while parsing an f-string.
+#[derive(Debug, Clone)]
+pub enum FStringErrorType {
+    /// `lambda` used inside an f-string replacement field without parentheses.
+    LambdaWithoutParentheses,
+    /// A conversion flag (`!s`/`!r`/`!a`) that isn't one of the three recognized ones.
+    InvalidConversionFlag,
+    /// An f-string whose `{` was never closed.
+    UnclosedLbrace,
+}
+
+impl std::fmt::Display for FStringErrorType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FStringErrorType::LambdaWithoutParentheses => {
+                write!(f, "lambda expressions are not allowed without parentheses")
+            }
+            FStringErrorType::InvalidConversionFlag => write!(f, "invalid conversion flag"),
+            FStringErrorType::UnclosedLbrace => write!(f, "expecting `}}`"),
+        }
+    }
+}
@@ -1,4 +1,6 @@
+use std::cell::Cell;
 use std::fmt::Display;
+use std::rc::Rc;
 
 use bitflags::bitflags;
 
@@ -17,7 +19,7 @@ use crate::{
         concatenated_strings, parse_fstring_literal_element, parse_string_literal, StringType,
     },
     token_set::TokenSet,
-    token_source::TokenSource,
+    token_source::{TokenSource, TokenSourceCheckpoint},
     Mode, ParseError, ParseErrorType, Tok, TokenKind,
 };
 
@@ -44,6 +46,9 @@ pub(crate) fn parse_tokens(
 pub struct Program {
     pub ast: ast::Mod,
     pub parse_errors: Vec<ParseError>,
+    /// Significant token positions collected if the parser opted into
+    /// [`Parser::with_token_positions`]; empty otherwise.
+    pub token_positions: Vec<TokenPosition>,
 }
 
 impl Program {
@@ -55,6 +60,21 @@ impl Program {
     pub fn parse_tokens(source: &str, tokens: Vec<LexResult>, mode: Mode) -> Program {
         Parser::new(source, mode, TokenSource::new(tokens)).parse()
     }
+
+    /// Like [`Program::parse_tokens`], but also populates [`Program::token_positions`]
+    /// with the significant token positions of decorators, function/class
+    /// definitions, and `try` statements. Intended for callers that need to
+    /// reconstruct source losslessly (e.g. a CST) without re-lexing; the extra
+    /// bookkeeping isn't worth paying for on the common path.
+    pub fn parse_tokens_with_token_positions(
+        source: &str,
+        tokens: Vec<LexResult>,
+        mode: Mode,
+    ) -> Program {
+        Parser::new(source, mode, TokenSource::new(tokens))
+            .with_token_positions()
+            .parse()
+    }
 }
 
 bitflags! {
@@ -69,6 +89,154 @@ bitflags! {
     }
 }
 
+bitflags! {
+    /// Tracks which constructs are forbidden at the current position, independent of
+    /// what kind of expression or statement is currently being parsed.
+    ///
+    /// This is deliberately kept separate from [`ParserCtxFlags`], which tracks *what*
+    /// the parser is currently inside (e.g. a tuple, a `for` target). `Restrictions`
+    /// instead tracks *where* a construct is forbidden from appearing, e.g. `yield` is
+    /// only legal inside a function body, and a bare starred expression is only legal
+    /// in a handful of positions. Consulting this up front lets the relevant parser
+    /// function emit a precise, on-the-spot error instead of parsing the construct and
+    /// rejecting it afterwards.
+    ///
+    /// There's intentionally no flag here for "a dict/set literal is ambiguous after a
+    /// keyword": this parser doesn't actually have a keyword-adjacent position where a
+    /// `{` is grammatically ambiguous between a literal and something else, so there's
+    /// no real restriction to encode. If a concrete ambiguous position turns up, add a
+    /// flag for it then rather than modelling one speculatively.
+    #[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+    struct Restrictions: u8 {
+        /// A `yield` or `yield from` expression is not allowed at the current position,
+        /// however deeply nested (e.g. inside a call's arguments) it may be.
+        const YIELD_EXPRESSION_FORBIDDEN = 1 << 0;
+
+        /// A starred expression (`*x`) is not allowed anywhere in the current
+        /// position, however deeply nested (e.g. inside a parenthesized tuple). Used
+        /// while parsing `del` targets, which can never contain one.
+        const STARRED_EXPRESSION_FORBIDDEN = 1 << 1;
+
+        /// A named expression (`x := y`) is not allowed anywhere in the current
+        /// position, however deeply nested. Used while parsing assignment targets
+        /// (e.g. a `for` loop's target), which can never be a named expression, even
+        /// a parenthesized one.
+        const NAMED_EXPRESSION_FORBIDDEN = 1 << 2;
+    }
+}
+
+/// An opaque snapshot of the parser's state, captured by [`Parser::checkpoint`] and
+/// restored by [`Parser::rewind`].
+///
+/// This enables speculative parsing: a routine can optimistically try one grammar
+/// production and, if it turns out to be the wrong one, rewind the parser to exactly
+/// where it started and try another, as if the first attempt never happened. Any
+/// tokens consumed and errors emitted during the abandoned attempt are discarded.
+#[derive(Debug)]
+struct Checkpoint {
+    tokens: TokenSourceCheckpoint,
+    current: Spanned,
+    last_token_end: TextSize,
+    ctx: ParserCtxFlags,
+    ctx_stack_len: usize,
+    restrictions: Restrictions,
+    errors_len: usize,
+    defer_invalid_node_creation: Option<TextRange>,
+    token_positions_len: usize,
+}
+
+/// RAII guard returned by [`Parser::recursion_guard`]. Decrements the shared
+/// recursion-depth counter on drop so every exit path out of a recursive parse
+/// function — including early returns and rewinds out of a speculative parse —
+/// releases its share of the depth budget.
+struct RecursionGuard {
+    depth: Rc<Cell<u32>>,
+}
+
+impl Drop for RecursionGuard {
+    fn drop(&mut self) {
+        self.depth.set(self.depth.get() - 1);
+    }
+}
+
+/// How confident the parser is that applying a [`Suggestion`] automatically would
+/// produce what the user meant, mirroring rustc's diagnostic applicability levels.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Applicability {
+    /// The suggested edit is unambiguously correct and can be applied automatically,
+    /// e.g. by an editor or formatter, without a human reviewing it.
+    MachineApplicable,
+    /// The suggested edit is probably, but not definitely, what the user meant.
+    MaybeIncorrect,
+}
+
+/// A structured, machine-applicable edit that would fix a [`ParseError`].
+///
+/// This lets downstream tooling (editors, the formatter, `ruff`'s own fixer) surface
+/// and apply a correction without having to re-derive it from the error's message.
+#[derive(Debug, Clone)]
+pub struct Suggestion {
+    /// The source range to replace.
+    pub range: TextRange,
+    /// The text to replace `range` with.
+    pub replacement: String,
+    pub applicability: Applicability,
+}
+
+/// The position of one significant token (a keyword, `:`, `->`, `(`, or `)`)
+/// belonging to a parsed node, collected when [`Parser::with_token_positions`]
+/// is enabled. `node` is the range of the enclosing node (e.g. a
+/// `StmtFunctionDef`), so positions can be grouped back up by consumers without
+/// a second pass over the token stream.
+///
+/// This is deliberately coarser than a full lossless token stream: it only
+/// records the handful of significant tokens (keywords and punctuation) whose
+/// exact offsets a CST or refactoring tool can't otherwise recover from a node's
+/// start/end `range` alone, rather than every token consumed while parsing it.
+#[derive(Debug, Clone, Copy)]
+pub struct TokenPosition {
+    pub node: TextRange,
+    pub kind: TokenKind,
+    pub offset: TextSize,
+}
+
+/// How [`Parser::parse_separated`] should recover when the separator between two
+/// elements isn't exactly what's expected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SeparatorRecovery {
+    /// Stop at the first unexpected token, abandoning the rest of the sequence. This
+    /// is correct for sequences where a malformed separator is unlikely to be a
+    /// simple typo (e.g. `global`/`nonlocal` names).
+    Strict,
+    /// Tolerate common separator mistakes so the rest of the sequence still parses:
+    /// a doubled delimiter (e.g. `[1,, 2]`) is reported and treated as a single one,
+    /// and an unexpected token between elements is skipped up to the next delimiter
+    /// or closing token instead of abandoning the sequence. Used for call arguments,
+    /// subscripts, and collection literals, where every element still matters for
+    /// tooling even after one of them was malformed.
+    Recovering,
+}
+
+/// How [`Parser::parse_delimited_match_pattern`] and
+/// [`Parser::parse_sequence_match_pattern`] recover when a match pattern's
+/// enclosing delimiters turn out to be wrong — mismatched (`case (1, 2]:`), or a
+/// `{}` that, on inspection, holds a comma-separated sequence rather than
+/// `key: value` pairs (`case {1, 2}:`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum MatchPatternDelimiterRecovery {
+    /// Fall back to the ordinary "expected token" diagnostic and abandon the rest
+    /// of the pattern. Used for the unparenthesized pattern list before a match
+    /// statement's `:`, which has no enclosing delimiter to mismatch in the first
+    /// place.
+    Strict,
+    /// Treat a mismatched or semantically-wrong delimiter as a malformed sequence
+    /// pattern: keep every element already parsed, synthesize a
+    /// `PatternMatchSequence` spanning the original delimiters, and attach a
+    /// suggested replacement for the delimiter pair instead of abandoning the
+    /// pattern.
+    Recovering,
+}
+
 type ExprWithRange = (ParsedExpr, TextRange);
 
 #[derive(Debug)]
@@ -173,10 +341,30 @@ pub(crate) struct Parser<'src> {
     /// Holds the range of the skipped tokens.
     defer_invalid_node_creation: Option<TextRange>,
 
+    /// Tracks constructs that are forbidden at the current position. See
+    /// [`Restrictions`] for more details.
+    restrictions: Restrictions,
+
     current: Spanned,
 
     /// The end of the last processed. Used to determine a node's end.
     last_token_end: TextSize,
+
+    /// Current depth of mutual recursion between pattern, expression, and nested
+    /// statement-body parsing. Guarded by [`Parser::recursion_guard`] so that
+    /// pathologically deep input (thousands of nested `[[[[...`) produces a
+    /// [`ParseErrorType::RecursionLimitExceeded`] error instead of overflowing the
+    /// native stack. `Rc<Cell<_>>` rather than a plain field so the returned guard
+    /// can outlive the `&mut self` borrow used to create it.
+    recursion_depth: Rc<Cell<u32>>,
+
+    /// Whether to collect [`TokenPosition`]s into `token_positions` while
+    /// parsing. Off by default; opt in with [`Parser::with_token_positions`].
+    collect_token_positions: bool,
+
+    /// Significant token positions collected so far, if `collect_token_positions`
+    /// is set. Handed off to [`Program::token_positions`] once parsing finishes.
+    token_positions: Vec<TokenPosition>,
 }
 
 const NEWLINE_EOF_SET: TokenSet = TokenSet::new(&[TokenKind::Newline, TokenKind::EndOfFile]);
@@ -205,8 +393,19 @@ const EXPR_SET: TokenSet = TokenSet::new(&[
     TokenKind::Lambda,
     TokenKind::Await,
     TokenKind::Not,
+    // Not a real Python prefix operator, but `parse_lhs` recovers it as `not`
+    // (see `Parser::parse_foreign_not_expr`), so it needs to be recognized as a
+    // possible expression start too.
+    TokenKind::Exclamation,
     TokenKind::Yield,
     TokenKind::FStringStart,
+    // Soft keywords: outside of a `match`/`case`/`type` statement header they're
+    // ordinary identifiers and so can start an expression, e.g. `return match`.
+    // `is_soft_keyword_at` is what actually disambiguates the two uses; this set
+    // only needs to admit the possibility.
+    TokenKind::Match,
+    TokenKind::Case,
+    TokenKind::Type,
 ])
 .union(LITERAL_SET);
 /// Tokens that can appear after an expression.
@@ -259,6 +458,36 @@ const SIMPLE_STMT_SET: TokenSet = TokenSet::new(&[
 ]);
 /// Tokens that represent simple statements, including expressions.
 const SIMPLE_STMT_SET2: TokenSet = SIMPLE_STMT_SET.union(EXPR_SET);
+/// Tokens that can start a new statement, or that mark the boundary of one (the end
+/// of a suite, or the source). Used to resynchronize after a statement that couldn't
+/// be parsed, without skipping past the statements that follow it.
+const STMT_BOUNDARY_SET: TokenSet = SIMPLE_STMT_SET2
+    .union(COMPOUND_STMT_SET)
+    .union(TokenSet::new(&[TokenKind::Dedent]))
+    .union(NEWLINE_EOF_SET);
+
+/// Marks a tuple recovered from an unparenthesized multi-value construct (e.g.
+/// `except A, B:`, `def f() -> int, str:`) as though it had been written with
+/// parentheses, mirroring the suggested fix. This keeps the node consistent
+/// with the one a corrected input would have produced, rather than leaving
+/// later passes to special-case an unparenthesized `Expr::Tuple` that's only
+/// valid here because of the error above it.
+fn recover_tuple_as_parenthesized(expr: &mut Expr) {
+    if let Expr::Tuple(tuple) = expr {
+        tuple.parenthesized = true;
+    }
+}
+
+/// The closing delimiter character for one of `)`, `]`, or `}`, for use in
+/// diagnostics and fix suggestions.
+fn closing_delimiter_char(closing: TokenKind) -> char {
+    match closing {
+        TokenKind::Rpar => ')',
+        TokenKind::Rsqb => ']',
+        TokenKind::Rbrace => '}',
+        _ => unreachable!("not a closing delimiter: {closing:?}"),
+    }
+}
 
 impl<'src> Parser<'src> {
     pub(crate) fn new(source: &'src str, mode: Mode, mut tokens: TokenSource) -> Parser<'src> {
@@ -278,8 +507,33 @@ impl<'src> Parser<'src> {
             current,
 
             defer_invalid_node_creation: None,
+            restrictions: Restrictions::empty(),
+            recursion_depth: Rc::new(Cell::new(0)),
+            collect_token_positions: false,
+            token_positions: Vec::new(),
+        }
+    }
+
+    /// Opts into collecting [`TokenPosition`]s for the keyword and punctuation
+    /// tokens of decorators, function/class definitions, and `try` statements.
+    /// Off by default; enable this for callers that need to reconstruct source
+    /// losslessly (e.g. a CST or a fine-grained refactoring tool) without
+    /// re-lexing.
+    pub(crate) fn with_token_positions(mut self) -> Self {
+        self.collect_token_positions = true;
+        self
+    }
+
+    /// Records the position of a significant token within `node`'s range, if
+    /// [`Parser::with_token_positions`] opted into collecting them. A no-op
+    /// otherwise.
+    fn record_token_position(&mut self, node: TextRange, kind: TokenKind, offset: TextSize) {
+        if self.collect_token_positions {
+            self.token_positions
+                .push(TokenPosition { node, kind, offset });
         }
     }
+
     fn finish(self) -> Vec<ParseError> {
         // After parsing, the `ctx` and `ctx_stack` should be empty.
         // If it's not, you probably forgot to call `clear_ctx` somewhere.
@@ -346,18 +600,7 @@ impl<'src> Parser<'src> {
                     continue;
                 }
 
-                body.push(self.parse_statement());
-
-                if let Some(range) = self.defer_invalid_node_creation {
-                    self.defer_invalid_node_creation = None;
-                    body.push(Stmt::Expr(ast::StmtExpr {
-                        value: Box::new(Expr::Invalid(ast::ExprInvalid {
-                            value: self.src_text(range).into(),
-                            range,
-                        })),
-                        range,
-                    }));
-                }
+                self.parse_statement_with_recovery(&mut body);
             }
             ast::Mod::Module(ast::ModModule {
                 body,
@@ -377,9 +620,12 @@ impl<'src> Parser<'src> {
             })
         };
 
+        let token_positions = std::mem::take(&mut self.token_positions);
+
         Program {
             ast,
             parse_errors: self.finish(),
+            token_positions,
         }
     }
 
@@ -398,6 +644,98 @@ impl<'src> Parser<'src> {
         }
     }
 
+    /// Captures the current parser state so that a speculative parse can be undone
+    /// with [`Parser::rewind`] if it turns out to be the wrong interpretation.
+    fn checkpoint(&self) -> Checkpoint {
+        Checkpoint {
+            tokens: self.tokens.checkpoint(),
+            current: self.current.clone(),
+            last_token_end: self.last_token_end,
+            ctx: self.ctx,
+            ctx_stack_len: self.ctx_stack.len(),
+            restrictions: self.restrictions,
+            errors_len: self.errors.len(),
+            defer_invalid_node_creation: self.defer_invalid_node_creation,
+            token_positions_len: self.token_positions.len(),
+        }
+    }
+
+    /// Restores the parser to the state captured by `checkpoint`. This discards any
+    /// tokens consumed and any errors recorded since the checkpoint was taken, so a
+    /// failed speculative parse is invisible to the rest of the parser.
+    fn rewind(&mut self, checkpoint: Checkpoint) {
+        self.tokens.rewind(checkpoint.tokens);
+        self.current = checkpoint.current;
+        self.last_token_end = checkpoint.last_token_end;
+        self.ctx = checkpoint.ctx;
+        self.ctx_stack.truncate(checkpoint.ctx_stack_len);
+        self.restrictions = checkpoint.restrictions;
+        self.errors.truncate(checkpoint.errors_len);
+        self.defer_invalid_node_creation = checkpoint.defer_invalid_node_creation;
+        self.token_positions.truncate(checkpoint.token_positions_len);
+    }
+
+    /// Absolute backstop on mutual recursion between pattern, expression, and
+    /// nested statement-body parsing, regardless of how much native stack
+    /// [`Parser::with_stack_headroom`] has grown into. Input nested this deep has
+    /// no realistic source behind it, so past this point we give up with a
+    /// [`ParseErrorType::RecursionLimitExceeded`] error instead of growing the
+    /// stack indefinitely.
+    const MAX_RECURSION_DEPTH: u32 = 100_000;
+
+    /// Extra native stack [`stacker::maybe_grow`] keeps in reserve before a
+    /// recursive parse function is entered; once less than this remains, a new
+    /// segment is allocated.
+    const STACK_RED_ZONE: usize = 64 * 1024;
+
+    /// Size of each stack segment [`stacker::maybe_grow`] allocates once
+    /// [`Parser::STACK_RED_ZONE`] is used up.
+    const STACK_SEGMENT_SIZE: usize = 1024 * 1024;
+
+    /// Runs `func` via [`stacker::maybe_grow`], so that mutual recursion between
+    /// pattern, expression, and nested statement-body parsing can go as deep as
+    /// the input genuinely requires — spilling onto a freshly allocated stack
+    /// segment as needed — instead of bailing out at a shallow fixed depth.
+    /// [`Parser::recursion_guard`]/[`Parser::MAX_RECURSION_DEPTH`] remain as an
+    /// absolute backstop against unbounded (rather than merely deep) recursion.
+    fn with_stack_headroom<T>(&mut self, func: impl FnOnce(&mut Self) -> T) -> T {
+        stacker::maybe_grow(Self::STACK_RED_ZONE, Self::STACK_SEGMENT_SIZE, move || {
+            func(self)
+        })
+    }
+
+    /// Guards against unbounded mutual recursion between pattern, expression, and
+    /// nested statement-body parsing. Call at the entry of a recursive parse
+    /// function; while the returned guard is held, [`Parser::recursion_depth_exceeded`]
+    /// reports whether the limit has been reached, and the counter is decremented
+    /// automatically on drop so an early return (or a rewind out of a speculative
+    /// parse) can't leave it stuck too high.
+    fn recursion_guard(&self) -> RecursionGuard {
+        self.recursion_depth.set(self.recursion_depth.get() + 1);
+        RecursionGuard {
+            depth: Rc::clone(&self.recursion_depth),
+        }
+    }
+
+    /// Whether the parser has recursed past [`Parser::MAX_RECURSION_DEPTH`] and
+    /// should bail out of the current construct instead of recursing further.
+    #[inline]
+    fn recursion_depth_exceeded(&self) -> bool {
+        self.recursion_depth.get() > Self::MAX_RECURSION_DEPTH
+    }
+
+    /// Reports a [`ParseErrorType::RecursionLimitExceeded`] at the current token,
+    /// skips to `recover_set` (typically `NEWLINE_EOF_SET` or an enclosing closing
+    /// delimiter), and returns the error's range together with the skipped range,
+    /// from which the caller builds its `Invalid` node.
+    fn recover_from_recursion_limit(&mut self, recover_set: TokenSet) -> (TextRange, TextRange) {
+        let error_range = self.current_range();
+        self.add_error(ParseErrorType::RecursionLimitExceeded, error_range);
+
+        let skipped_range = self.skip_until(NEWLINE_EOF_SET.union(recover_set));
+        (error_range, skipped_range)
+    }
+
     /// Returns the start position for a node that starts at the current token.
     fn node_start(&self) -> TextSize {
         self.current_range().start()
@@ -412,6 +750,25 @@ impl<'src> Parser<'src> {
         self.ctx.intersects(ctx)
     }
 
+    /// Runs `func` with `restrictions` added to the current set of [`Restrictions`],
+    /// restoring the previous set once `func` returns.
+    fn with_restrictions<T>(
+        &mut self,
+        restrictions: Restrictions,
+        func: impl FnOnce(&mut Self) -> T,
+    ) -> T {
+        let saved_restrictions = self.restrictions;
+        self.restrictions |= restrictions;
+        let result = func(self);
+        self.restrictions = saved_restrictions;
+        result
+    }
+
+    #[inline]
+    fn has_restriction(&self, restriction: Restrictions) -> bool {
+        self.restrictions.intersects(restriction)
+    }
+
     /// Moves the parser to the next token. Returns the old current token as an owned value.
     fn next_token(&mut self) -> Spanned {
         let next = self
@@ -494,7 +851,22 @@ impl<'src> Parser<'src> {
         }
 
         let (found, range) = self.current_token();
-        self.add_error(ParseErrorType::ExpectedToken { found, expected }, range);
+        // A missing `:` is one of the most common typos, and the fix is always the
+        // same: insert it right after the previous token. Surface that as a
+        // machine-applicable suggestion instead of leaving it for the user to find.
+        if expected == TokenKind::Colon {
+            self.add_error_with_suggestion(
+                ParseErrorType::ExpectedToken { found, expected },
+                range,
+                Suggestion {
+                    range: TextRange::empty(self.last_token_end),
+                    replacement: ":".to_string(),
+                    applicability: Applicability::MachineApplicable,
+                },
+            );
+        } else {
+            self.add_error(ParseErrorType::ExpectedToken { found, expected }, range);
+        }
         false
     }
 
@@ -508,9 +880,14 @@ impl<'src> Parser<'src> {
             let range = self.skip_until(expected_set);
             self.defer_invalid_node_creation = Some(range);
 
-            self.add_error(
+            self.add_error_with_suggestion(
                 ParseErrorType::OtherError("unexpected tokens".into()),
                 range,
+                Suggestion {
+                    range,
+                    replacement: String::new(),
+                    applicability: Applicability::MaybeIncorrect,
+                },
             );
 
             self.eat(expected);
@@ -524,6 +901,24 @@ impl<'src> Parser<'src> {
         self.errors.push(ParseError {
             error,
             location: ranged.range(),
+            suggestions: Vec::new(),
+        });
+    }
+
+    /// Like [`Parser::add_error`], but attaches a structured, potentially
+    /// machine-applicable fix `suggestion` to the diagnostic.
+    fn add_error_with_suggestion<T>(
+        &mut self,
+        error: ParseErrorType,
+        ranged: T,
+        suggestion: Suggestion,
+    ) where
+        T: Ranged,
+    {
+        self.errors.push(ParseError {
+            error,
+            location: ranged.range(),
+            suggestions: vec![suggestion],
         });
     }
 
@@ -558,6 +953,123 @@ impl<'src> Parser<'src> {
         self.at_ts(COMPOUND_STMT_SET)
     }
 
+    /// How far [`Parser::looks_like_match_stmt_header`] and
+    /// [`Parser::looks_like_type_alias_stmt_header`] will look ahead before giving
+    /// up and treating the soft keyword as a plain name.
+    const MAX_SOFT_KEYWORD_LOOKAHEAD: usize = 64;
+
+    /// Returns whether the current soft keyword `kind` (`match`, `case`, or `type`)
+    /// is actually being used as an ordinary name, rather than introducing the
+    /// construct it can also spell. Callers are expected to only call this when
+    /// positioned at the first token of a statement, which is the only place any
+    /// of these can introduce their construct; `parse_match_pattern_literal`, for
+    /// instance, never calls this, because `match`/`case`/`type` are unconditionally
+    /// names wherever a pattern literal is expected.
+    fn is_soft_keyword_at(&mut self, kind: TokenKind) -> bool {
+        match kind {
+            TokenKind::Match => !self.looks_like_match_stmt_header(),
+            TokenKind::Type => !self.looks_like_type_alias_stmt_header(),
+            // `case` only ever introduces a `case` clause directly inside a
+            // `match` body, which callers already know from their own position in
+            // the grammar (see `parse_match_cases`); elsewhere it's a name.
+            TokenKind::Case => true,
+            _ => false,
+        }
+    }
+
+    /// Scans forward from the current `match` token to see whether what follows
+    /// can be read as `<subject-expr> :` before the end of the logical line, which
+    /// is the only shape a match statement's header can take. A bare `match(x)` or
+    /// `match = x` fails this check and is parsed as an ordinary expression
+    /// statement instead.
+    fn looks_like_match_stmt_header(&mut self) -> bool {
+        let mut depth = 0u32;
+        for offset in 1..=Self::MAX_SOFT_KEYWORD_LOOKAHEAD {
+            match self.peek_nth(offset).0 {
+                TokenKind::Lpar | TokenKind::Lsqb | TokenKind::Lbrace => depth += 1,
+                TokenKind::Rpar | TokenKind::Rsqb | TokenKind::Rbrace => {
+                    depth = depth.saturating_sub(1);
+                }
+                TokenKind::Colon if depth == 0 => return true,
+                TokenKind::Equal if depth == 0 => return false,
+                TokenKind::Newline | TokenKind::EndOfFile if depth == 0 => return false,
+                _ => {}
+            }
+        }
+        // Ran out of lookahead without finding anything that rules a match
+        // statement out (an `=` or a line end at depth 0). A subject expression
+        // long enough to exhaust `MAX_SOFT_KEYWORD_LOOKAHEAD` is unusual but
+        // valid Python, so default to assuming it *is* a match statement --
+        // misparsing a real one as a plain name produces spurious errors on
+        // valid input, while the reverse (parsing `match` as a name when it
+        // wasn't) only matters for code that was already this convoluted.
+        true
+    }
+
+    /// Scans forward from the current `type` token to see whether what follows can
+    /// be read as `NAME [ type-params ] =`, which is the only shape a type alias
+    /// statement's header can take. A bare `type(x)` or `type = x` fails this
+    /// check and is parsed as an ordinary expression statement instead.
+    fn looks_like_type_alias_stmt_header(&mut self) -> bool {
+        if self.peek_nth(1).0 != TokenKind::Name {
+            return false;
+        }
+
+        let mut offset = 2;
+        if self.peek_nth(offset).0 == TokenKind::Lsqb {
+            let mut depth = 0u32;
+            loop {
+                match self.peek_nth(offset).0 {
+                    TokenKind::Lsqb => depth += 1,
+                    TokenKind::Rsqb => {
+                        depth -= 1;
+                        if depth == 0 {
+                            offset += 1;
+                            break;
+                        }
+                    }
+                    TokenKind::Newline | TokenKind::EndOfFile => return false,
+                    _ => {}
+                }
+                offset += 1;
+                if offset - 2 > Self::MAX_SOFT_KEYWORD_LOOKAHEAD {
+                    return false;
+                }
+            }
+        }
+
+        self.peek_nth(offset).0 == TokenKind::Equal
+    }
+
+    /// Peeks past a match pattern's opening `{` to see whether it holds a real
+    /// mapping pattern (`**rest`, a `key: value` pair, or an empty `{}`) rather
+    /// than a set-literal-style sequence pattern that was never valid as a
+    /// mapping to begin with, e.g. `case {1, 2}:`. The latter is routed through
+    /// [`Parser::parse_delimited_match_pattern`]'s delimiter recovery instead.
+    fn looks_like_match_pattern_mapping(&mut self) -> bool {
+        if matches!(
+            self.peek_nth(1).0,
+            TokenKind::Rbrace | TokenKind::DoubleStar
+        ) {
+            return true;
+        }
+
+        let mut depth = 0u32;
+        for offset in 1..=Self::MAX_SOFT_KEYWORD_LOOKAHEAD {
+            match self.peek_nth(offset).0 {
+                TokenKind::Lpar | TokenKind::Lsqb | TokenKind::Lbrace => depth += 1,
+                TokenKind::Rpar | TokenKind::Rsqb => depth = depth.saturating_sub(1),
+                TokenKind::Rbrace if depth == 0 => return true,
+                TokenKind::Rbrace => depth = depth.saturating_sub(1),
+                TokenKind::Colon if depth == 0 => return true,
+                TokenKind::Comma if depth == 0 => return false,
+                TokenKind::Newline | TokenKind::EndOfFile => return true,
+                _ => {}
+            }
+        }
+        true
+    }
+
     fn src_text<T>(&self, ranged: T) -> &'src str
     where
         T: Ranged,
@@ -593,6 +1105,7 @@ impl<'src> Parser<'src> {
         opening: TokenKind,
         delim: TokenKind,
         closing: TokenKind,
+        recovery: SeparatorRecovery,
         mut func: impl FnMut(&mut Parser<'src>),
     ) -> TextRange {
         let start_range = self.current_range();
@@ -602,6 +1115,7 @@ impl<'src> Parser<'src> {
             allow_trailing_delim,
             delim,
             [closing].as_slice(),
+            recovery,
             |parser| {
                 func(parser);
                 // Doesn't matter what range we return here
@@ -620,6 +1134,9 @@ impl<'src> Parser<'src> {
     /// encounter the tokens in `ending_set` it stops parsing when seeing the `EOF`
     /// or `Newline` token.
     ///
+    /// `recovery` controls what happens when the separator between two elements
+    /// isn't what's expected; see [`SeparatorRecovery`] for the available modes.
+    ///
     /// Returns the last [`TextRange`] of the parsed elements. If none elements are
     /// parsed it returns `None`.
     fn parse_separated(
@@ -627,6 +1144,7 @@ impl<'src> Parser<'src> {
         allow_trailing_delim: bool,
         delim: TokenKind,
         ending_set: impl Into<TokenSet>,
+        recovery: SeparatorRecovery,
         mut func: impl FnMut(&mut Parser<'src>) -> TextRange,
     ) -> Option<TextRange> {
         let ending_set = NEWLINE_EOF_SET.union(ending_set.into());
@@ -643,23 +1161,46 @@ impl<'src> Parser<'src> {
             if self.at(delim) {
                 final_range = Some(self.current_range());
                 self.eat(delim);
-            } else {
-                if self.at_expr() {
-                    self.expect(delim);
-                } else {
-                    break;
+
+                // Recover from a doubled delimiter (e.g. `[1,, 2]`) by treating it
+                // as a single one instead of looping back around to parse an empty
+                // element.
+                if recovery == SeparatorRecovery::Recovering {
+                    while self.at(delim) {
+                        self.add_error(
+                            ParseErrorType::OtherError(format!("unexpected extra `{delim:?}`")),
+                            self.current_range(),
+                        );
+                        final_range = Some(self.current_range());
+                        self.eat(delim);
+                    }
                 }
+            } else if self.at_expr() {
+                self.expect(delim);
+            } else if recovery == SeparatorRecovery::Recovering && !self.at_ts(ending_set) {
+                // Skip the unexpected token(s) up to the next delimiter or one of
+                // the ending tokens, rather than abandoning the rest of the
+                // sequence outright.
+                let recover_set = ending_set.union([delim].as_slice().into());
+                let skipped_range = self.skip_until(recover_set);
+                self.add_error(
+                    ParseErrorType::OtherError("unexpected token in sequence".to_string()),
+                    skipped_range,
+                );
+                final_range = Some(skipped_range);
+            } else {
+                break;
             }
         }
 
         final_range
     }
 
-    fn is_current_token_postfix(&self) -> bool {
+    fn is_current_token_postfix(&mut self) -> bool {
         matches!(
             self.current_kind(),
             TokenKind::Lpar | TokenKind::Lsqb | TokenKind::Dot | TokenKind::Async | TokenKind::For
-        )
+        ) || self.at_foreign_increment_or_decrement()
     }
 
     fn handle_unexpected_indentation(&mut self, stmts: &mut Vec<Stmt>, error_msg: &str) {
@@ -671,14 +1212,85 @@ impl<'src> Parser<'src> {
         );
 
         while !self.at(TokenKind::Dedent) && !self.at(TokenKind::EndOfFile) {
-            let stmt = self.parse_statement();
-            stmts.push(stmt);
+            self.parse_statement_with_recovery(stmts);
         }
 
         assert!(self.eat(TokenKind::Dedent));
     }
 
+    /// Parses a single statement and resynchronizes to the start of the next one
+    /// afterwards, pushing the result (and, if recovery kicked in, a trailing
+    /// `Invalid` node) onto `stmts`.
+    ///
+    /// Adapted from rustc's `AttemptLocalParseRecovery`: rather than letting a
+    /// malformed statement's error recovery (`skip_until`, bounded only by
+    /// `Newline`/EOF) run unchecked, this stops as soon as the parser reaches a
+    /// token in [`STMT_BOUNDARY_SET`] — a token that can start a new statement, a
+    /// `Dedent`, or the end of the source — so a single broken line doesn't
+    /// swallow the statements that follow it into one `Invalid` node.
+    ///
+    /// Returns whether the parser resynchronized at a statement boundary; since the
+    /// end of the source is itself such a boundary, this is always `true`.
+    fn parse_statement_with_recovery(&mut self, stmts: &mut Vec<Stmt>) -> bool {
+        stmts.push(self.parse_statement());
+
+        if let Some(range) = self.defer_invalid_node_creation.take() {
+            stmts.push(Stmt::Expr(ast::StmtExpr {
+                value: Box::new(Expr::Invalid(ast::ExprInvalid {
+                    value: self.src_text(range).into(),
+                    range,
+                })),
+                range,
+            }));
+        }
+
+        if self.at_ts(STMT_BOUNDARY_SET) {
+            return true;
+        }
+
+        // `parse_statement` left the parser in the middle of this line; skip the
+        // leftover tokens up to the next statement boundary instead of letting the
+        // following `parse_statement` call choke on, or silently reinterpret, what's
+        // actually trailing garbage from the error above.
+        let range = self.skip_until(STMT_BOUNDARY_SET);
+        self.add_error(
+            ParseErrorType::OtherError("unexpected tokens".to_string()),
+            range,
+        );
+        stmts.push(Stmt::Expr(ast::StmtExpr {
+            value: Box::new(Expr::Invalid(ast::ExprInvalid {
+                value: self.src_text(range).into(),
+                range,
+            })),
+            range,
+        }));
+
+        // The boundary reached may be the `Newline` ending this broken line; consume
+        // it so the enclosing suite goes on to the next statement instead of taking
+        // that `Newline` as its own end.
+        let resynchronized = self.at_ts(STMT_BOUNDARY_SET);
+        self.eat(TokenKind::Newline);
+        resynchronized
+    }
+
     fn parse_statement(&mut self) -> Stmt {
+        let _depth_guard = self.recursion_guard();
+        if self.recursion_depth_exceeded() {
+            let (error_range, skipped_range) = self.recover_from_recursion_limit(TokenSet::EMPTY);
+            let range = error_range.cover(skipped_range);
+            return Stmt::Expr(ast::StmtExpr {
+                value: Box::new(Expr::Invalid(ast::ExprInvalid {
+                    value: self.src_text(range).into(),
+                    range,
+                })),
+                range,
+            });
+        }
+
+        self.with_stack_headroom(Self::parse_statement_impl)
+    }
+
+    fn parse_statement_impl(&mut self) -> Stmt {
         let start_offset = self.node_start();
         match self.current_kind() {
             TokenKind::If => Stmt::If(self.parse_if_stmt()),
@@ -690,7 +1302,9 @@ impl<'src> Parser<'src> {
             TokenKind::While => Stmt::While(self.parse_while_stmt()),
             TokenKind::Def => Stmt::FunctionDef(self.parse_func_def_stmt(vec![], start_offset)),
             TokenKind::Class => Stmt::ClassDef(self.parse_class_def_stmt(vec![], start_offset)),
-            TokenKind::Match => Stmt::Match(self.parse_match_stmt()),
+            TokenKind::Match if !self.is_soft_keyword_at(TokenKind::Match) => {
+                Stmt::Match(self.parse_match_stmt())
+            }
             _ => self.parse_simple_stmt_newline(),
         }
     }
@@ -874,6 +1488,27 @@ impl<'src> Parser<'src> {
                     range,
                 )
             }
+            // `match`, `case`, and `type` are soft keywords: within a pattern
+            // they're never anything other than a capture (or dotted value)
+            // pattern name, so route them through the same handling as `Tok::Name`
+            // instead of falling into the "invalid pattern literal" case below.
+            Tok::Match | Tok::Case | Tok::Type if self.at(TokenKind::Dot) => {
+                let id = Expr::Name(ast::ExprName {
+                    id: self.src_text(range).to_string(),
+                    ctx: ExprContext::Load,
+                    range,
+                });
+
+                let attribute = self.parse_attr_expr_for_match_pattern(id, start);
+                let range = attribute.range();
+                (
+                    Pattern::MatchValue(ast::PatternMatchValue {
+                        value: Box::new(attribute),
+                        range,
+                    }),
+                    range,
+                )
+            }
             Tok::Name { name } => (
                 Pattern::MatchAs(ast::PatternMatchAs {
                     range,
@@ -886,6 +1521,17 @@ impl<'src> Parser<'src> {
                 }),
                 range,
             ),
+            Tok::Match | Tok::Case | Tok::Type => (
+                Pattern::MatchAs(ast::PatternMatchAs {
+                    range,
+                    pattern: None,
+                    name: Some(ast::Identifier {
+                        id: self.src_text(range).to_string(),
+                        range,
+                    }),
+                }),
+                range,
+            ),
             Tok::Minus
                 if matches!(
                     self.current_kind(),
@@ -930,26 +1576,32 @@ impl<'src> Parser<'src> {
         }
     }
 
+    /// Parses a pattern enclosed in `()`, `[]`, or (when [`Parser::parse_match_pattern_lhs`]
+    /// has already determined it isn't a real mapping) `{}`.
+    ///
+    /// The last case only ever arrives here for a malformed mapping pattern like
+    /// `case {1, 2}:`, where `{}` was never valid delimiter syntax for a sequence
+    /// pattern; it's recovered the same way a mismatched closing delimiter is —
+    /// by keeping every element parsed so far and suggesting the corrected
+    /// delimiter pair.
     fn parse_delimited_match_pattern(&mut self) -> (Pattern, TextRange) {
-        let mut range = self.current_range();
-
-        let is_paren = self.at(TokenKind::Lpar);
-        let is_bracket = self.at(TokenKind::Lsqb);
-
-        let closing = if is_paren {
-            self.eat(TokenKind::Lpar);
-            TokenKind::Rpar
-        } else {
-            self.eat(TokenKind::Lsqb);
-            TokenKind::Rsqb
+        let opening_range = self.current_range();
+        let mut range = opening_range;
+
+        let opening = self.current_kind();
+        let closing = match opening {
+            TokenKind::Lpar => TokenKind::Rpar,
+            TokenKind::Lsqb => TokenKind::Rsqb,
+            _ => TokenKind::Rbrace,
         };
+        self.next_token();
 
         if matches!(self.current_kind(), TokenKind::Newline | TokenKind::Colon) {
             let range = self.current_range();
             self.add_error(
                 ParseErrorType::OtherError(format!(
                     "missing `{}`",
-                    if is_paren { ')' } else { ']' }
+                    closing_delimiter_char(closing)
                 )),
                 range,
             );
@@ -970,12 +1622,17 @@ impl<'src> Parser<'src> {
 
         let (mut pattern, pattern_range) = self.parse_match_pattern();
 
-        if is_bracket || self.at(TokenKind::Comma) {
-            (pattern, _) = self.parse_sequence_match_pattern(pattern, pattern_range, closing);
+        if opening != TokenKind::Lpar || self.at(TokenKind::Comma) {
+            (pattern, _) = self.parse_sequence_match_pattern(
+                pattern,
+                pattern_range,
+                closing,
+                MatchPatternDelimiterRecovery::Recovering,
+            );
         }
 
         range = range.cover(self.current_range());
-        self.expect_and_recover(closing, TokenSet::EMPTY);
+        range = self.recover_match_pattern_delimiter(opening, opening_range, closing, range);
 
         if let Pattern::MatchSequence(mut sequence) = pattern {
             // Update the range to include the parenthesis or brackets
@@ -986,11 +1643,81 @@ impl<'src> Parser<'src> {
         }
     }
 
+    /// Expects the closing delimiter of a match pattern sequence, recovering when
+    /// it's missing or wrong: a mismatched closing bracket (`case (1, 2]:`) is
+    /// corrected in place, and `{}` used for what turned out to be a sequence
+    /// pattern (`case {1, 2}:`) gets the whole delimiter pair suggested as
+    /// `[]`. Returns the range covering up to (and including, if present) the
+    /// delimiter that was consumed.
+    fn recover_match_pattern_delimiter(
+        &mut self,
+        opening: TokenKind,
+        opening_range: TextRange,
+        closing: TokenKind,
+        range: TextRange,
+    ) -> TextRange {
+        if opening == TokenKind::Lbrace {
+            // `{...}` is never valid sequence-pattern syntax, regardless of
+            // whether its own closer matches; suggest the canonical `[...]`.
+            let found = self.current_kind();
+            let (found_range, range) = if found == TokenKind::Rbrace
+                || found == TokenKind::Rpar
+                || found == TokenKind::Rsqb
+            {
+                let found_range = self.current_range();
+                self.next_token();
+                (found_range, range.cover(found_range))
+            } else {
+                (self.current_range(), range)
+            };
+
+            let inner = self.src_text(TextRange::new(opening_range.end(), found_range.start()));
+            self.add_error_with_suggestion(
+                ParseErrorType::MismatchedMatchPatternDelimiter {
+                    expected: TokenKind::Lsqb,
+                    found: opening,
+                },
+                range,
+                Suggestion {
+                    range,
+                    replacement: format!("[{inner}]"),
+                    applicability: Applicability::MaybeIncorrect,
+                },
+            );
+            return range;
+        }
+
+        let found = self.current_kind();
+        if found != closing
+            && matches!(found, TokenKind::Rpar | TokenKind::Rsqb | TokenKind::Rbrace)
+        {
+            let found_range = self.current_range();
+            self.next_token();
+            self.add_error_with_suggestion(
+                ParseErrorType::MismatchedMatchPatternDelimiter {
+                    expected: closing,
+                    found,
+                },
+                found_range,
+                Suggestion {
+                    range: found_range,
+                    replacement: closing_delimiter_char(closing).to_string(),
+                    applicability: Applicability::MachineApplicable,
+                },
+            );
+            return range.cover(found_range);
+        }
+
+        self.expect_and_recover(closing, TokenSet::EMPTY);
+        range
+    }
+
     fn parse_sequence_match_pattern(
         &mut self,
         first_elt: Pattern,
         elt_range: TextRange,
         ending: TokenKind,
+        recovery: MatchPatternDelimiterRecovery,
     ) -> (Pattern, TextRange) {
         // In case of the match sequence only having one element, we need to cover
         // the range of the comma.
@@ -998,11 +1725,29 @@ impl<'src> Parser<'src> {
         self.eat(TokenKind::Comma);
         let mut patterns = vec![first_elt];
 
-        let range = self.parse_separated(true, TokenKind::Comma, [ending].as_slice(), |parser| {
-            let (pattern, pattern_range) = parser.parse_match_pattern();
-            patterns.push(pattern);
-            pattern_range
-        });
+        // Under delimiter recovery, stop at any closing delimiter, not just `ending`,
+        // so a mismatched closer (`case (1, 2]:`) is left for
+        // `recover_match_pattern_delimiter` to report instead of being skipped.
+        let (separator_recovery, ending_set) = match recovery {
+            MatchPatternDelimiterRecovery::Strict => {
+                (SeparatorRecovery::Strict, TokenSet::new(&[ending]))
+            }
+            MatchPatternDelimiterRecovery::Recovering => (
+                SeparatorRecovery::Recovering,
+                TokenSet::new(&[ending, TokenKind::Rpar, TokenKind::Rsqb, TokenKind::Rbrace]),
+            ),
+        };
+        let range = self.parse_separated(
+            true,
+            TokenKind::Comma,
+            ending_set,
+            separator_recovery,
+            |parser| {
+                let (pattern, pattern_range) = parser.parse_match_pattern();
+                patterns.push(pattern);
+                pattern_range
+            },
+        );
         final_range = final_range.cover(range.unwrap_or(final_range));
 
         (
@@ -1015,10 +1760,34 @@ impl<'src> Parser<'src> {
     }
 
     fn parse_match_pattern_lhs(&mut self) -> (Pattern, TextRange) {
+        let _depth_guard = self.recursion_guard();
+        if self.recursion_depth_exceeded() {
+            let (error_range, skipped_range) = self.recover_from_recursion_limit(TokenSet::EMPTY);
+            let range = error_range.cover(skipped_range);
+            return (
+                Pattern::MatchValue(ast::PatternMatchValue {
+                    value: Box::new(Expr::Invalid(ast::ExprInvalid {
+                        value: self.src_text(range).into(),
+                        range,
+                    })),
+                    range,
+                }),
+                range,
+            );
+        }
+
+        self.with_stack_headroom(Self::parse_match_pattern_lhs_impl)
+    }
+
+    fn parse_match_pattern_lhs_impl(&mut self) -> (Pattern, TextRange) {
         let (mut lhs, mut range) = match self.current_kind() {
-            TokenKind::Lbrace => self.parse_match_pattern_mapping(),
+            TokenKind::Lbrace if self.looks_like_match_pattern_mapping() => {
+                self.parse_match_pattern_mapping()
+            }
             TokenKind::Star => self.parse_match_pattern_star(),
-            TokenKind::Lpar | TokenKind::Lsqb => self.parse_delimited_match_pattern(),
+            TokenKind::Lpar | TokenKind::Lsqb | TokenKind::Lbrace => {
+                self.parse_delimited_match_pattern()
+            }
             _ => self.parse_match_pattern_literal(),
         };
 
@@ -1026,59 +1795,54 @@ impl<'src> Parser<'src> {
             (lhs, range) = self.parse_match_pattern_class(lhs, range);
         }
 
-        if self.at(TokenKind::Plus) || self.at(TokenKind::Minus) {
+        // `1+2j`-style complex number literals are the only thing a `+`/`-` can
+        // legally continue here. Since a lone sign is otherwise a plausible start of
+        // the *next* token (e.g. a stray operator left over from a typo), only
+        // commit to parsing a rhs pattern once we already know the lhs is a literal
+        // that could plausibly be one operand of such a literal; speculatively parse
+        // the rhs and rewind if it doesn't turn out to be one either, so a bad guess
+        // here doesn't manufacture a bogus `BinOp` pattern and a cascade of errors.
+        if (self.at(TokenKind::Plus) || self.at(TokenKind::Minus))
+            && matches!(
+                &lhs,
+                Pattern::MatchValue(value)
+                    if value.value.is_literal_expr() || matches!(value.value.as_ref(), Expr::UnaryOp(_))
+            )
+        {
+            let checkpoint = self.checkpoint();
+
             let (op_kind, _) = self.next_token();
+            let (lhs_value, lhs_range) = match lhs {
+                Pattern::MatchValue(lhs) => (lhs.value, lhs.range),
+                _ => unreachable!("checked by the `matches!` guard above"),
+            };
 
-            let (lhs_value, lhs_range) = if let Pattern::MatchValue(lhs) = lhs {
-                if !lhs.value.is_literal_expr() && !matches!(lhs.value.as_ref(), Expr::UnaryOp(_)) {
-                    self.add_error(
-                        ParseErrorType::OtherError(format!(
-                            "invalid `{}` expression for match pattern",
-                            self.src_text(lhs.range)
-                        )),
-                        lhs.range,
-                    );
-                }
-                (lhs.value, lhs.range)
-            } else {
-                self.add_error(
-                    ParseErrorType::OtherError("invalid lhs pattern".to_string()),
-                    range,
+            let (rhs_pattern, rhs_range) = self.parse_match_pattern_lhs();
+
+            let Pattern::MatchValue(rhs) = rhs_pattern else {
+                // The rhs isn't a value pattern at all, so this was never a complex
+                // literal to begin with; rewind and let the caller deal with the
+                // dangling `+`/`-` as an unexpected token instead.
+                self.rewind(checkpoint);
+                return (
+                    Pattern::MatchValue(ast::PatternMatchValue {
+                        value: lhs_value,
+                        range: lhs_range,
+                    }),
+                    lhs_range,
                 );
-                (
-                    Box::new(Expr::Invalid(ast::ExprInvalid {
-                        value: self.src_text(range).into(),
-                        range,
-                    })),
-                    range,
-                )
             };
 
-            let (rhs_pattern, rhs_range) = self.parse_match_pattern_lhs();
-            let (rhs_value, rhs_range) = if let Pattern::MatchValue(rhs) = rhs_pattern {
-                if !rhs.value.is_literal_expr() {
-                    self.add_error(
-                        ParseErrorType::OtherError(format!(
-                            "invalid `{}` expression for match pattern",
-                            self.src_text(rhs_range)
-                        )),
-                        rhs_range,
-                    );
-                }
-                (rhs.value, rhs.range)
-            } else {
+            if !rhs.value.is_literal_expr() {
                 self.add_error(
-                    ParseErrorType::OtherError("invalid rhs pattern".to_string()),
-                    rhs_range,
+                    ParseErrorType::OtherError(format!(
+                        "invalid `{}` expression for match pattern",
+                        self.src_text(rhs.range)
+                    )),
+                    rhs.range,
                 );
-                (
-                    Box::new(Expr::Invalid(ast::ExprInvalid {
-                        value: self.src_text(rhs_range).into(),
-                        range: rhs_range,
-                    })),
-                    rhs_range,
-                )
-            };
+            }
+            let (rhs_value, rhs_range) = (rhs.value, rhs.range);
 
             if matches!(
                 rhs_value.as_ref(),
@@ -1150,7 +1914,12 @@ impl<'src> Parser<'src> {
         let (pattern, range) = self.parse_match_pattern();
 
         if self.at(TokenKind::Comma) {
-            return self.parse_sequence_match_pattern(pattern, range, TokenKind::Colon);
+            return self.parse_sequence_match_pattern(
+                pattern,
+                range,
+                TokenKind::Colon,
+                MatchPatternDelimiterRecovery::Strict,
+            );
         }
 
         (pattern, range)
@@ -1191,6 +1960,7 @@ impl<'src> Parser<'src> {
             TokenKind::Lpar,
             TokenKind::Comma,
             TokenKind::Rpar,
+            SeparatorRecovery::Strict,
             |parser| {
                 let (pattern, pattern_range) = parser.parse_match_pattern();
 
@@ -1302,6 +2072,7 @@ impl<'src> Parser<'src> {
             TokenKind::Lbrace,
             TokenKind::Comma,
             TokenKind::Rbrace,
+            SeparatorRecovery::Strict,
             |parser| {
                 if parser.eat(TokenKind::DoubleStar) {
                     rest = Some(parser.parse_identifier());
@@ -1422,11 +2193,14 @@ impl<'src> Parser<'src> {
         self.bump(TokenKind::For);
 
         self.set_ctx(ParserCtxFlags::FOR_TARGET);
-        let mut target = self.parse_expr_with_recovery(
-            Parser::parse_exprs,
-            [TokenKind::In, TokenKind::Colon].as_slice(),
-            "expecting expression after `for` keyword",
-        );
+        let mut target =
+            self.with_restrictions(Restrictions::NAMED_EXPRESSION_FORBIDDEN, |parser| {
+                parser.parse_expr_with_recovery(
+                    Parser::parse_exprs,
+                    [TokenKind::In, TokenKind::Colon].as_slice(),
+                    "expecting expression after `for` keyword",
+                )
+            });
         self.clear_ctx(ParserCtxFlags::FOR_TARGET);
 
         helpers::set_expr_ctx(&mut target.expr, ExprContext::Store);
@@ -1463,7 +2237,9 @@ impl<'src> Parser<'src> {
 
     fn parse_try_stmt(&mut self) -> ast::StmtTry {
         let try_start = self.node_start();
+        let try_offset = self.current_range().start();
         self.bump(TokenKind::Try);
+        let try_colon_offset = self.current_range().start();
         self.expect_and_recover(TokenKind::Colon, TokenSet::EMPTY);
 
         let mut is_star = false;
@@ -1475,6 +2251,7 @@ impl<'src> Parser<'src> {
         let mut handlers = vec![];
         loop {
             let except_start = self.node_start();
+            let except_offset = self.current_range().start();
             if self.eat(TokenKind::Except) {
                 has_except = true;
             } else {
@@ -1486,29 +2263,43 @@ impl<'src> Parser<'src> {
             let type_ = if self.at(TokenKind::Colon) && !is_star {
                 None
             } else {
-                let parsed_expr = self.parse_exprs();
+                let mut parsed_expr = self.parse_exprs();
                 if !parsed_expr.is_parenthesized && matches!(parsed_expr.expr, Expr::Tuple(_)) {
-                    self.add_error(
+                    let range = parsed_expr.expr.range();
+                    self.add_error_with_suggestion(
                         ParseErrorType::OtherError(
                             "multiple exception types must be parenthesized".to_string(),
                         ),
-                        &parsed_expr.expr,
+                        range,
+                        Suggestion {
+                            range,
+                            replacement: format!("({})", self.src_text(range)),
+                            applicability: Applicability::MachineApplicable,
+                        },
                     );
+                    recover_tuple_as_parenthesized(&mut parsed_expr.expr);
                 }
                 Some(Box::new(parsed_expr.expr))
             };
 
+            let as_offset = self.at(TokenKind::As).then(|| self.current_range().start());
             let name = if self.eat(TokenKind::As) {
                 Some(self.parse_identifier())
             } else {
                 None
             };
 
+            let except_colon_offset = self.current_range().start();
             self.expect_and_recover(TokenKind::Colon, TokenSet::EMPTY);
 
             let except_body = self.parse_body(Clause::Except);
 
             let except_range = self.node_range(except_start);
+            self.record_token_position(except_range, TokenKind::Except, except_offset);
+            if let Some(as_offset) = as_offset {
+                self.record_token_position(except_range, TokenKind::As, as_offset);
+            }
+            self.record_token_position(except_range, TokenKind::Colon, except_colon_offset);
             handlers.push(ExceptHandler::ExceptHandler(
                 ast::ExceptHandlerExceptHandler {
                     type_,
@@ -1523,7 +2314,12 @@ impl<'src> Parser<'src> {
             }
         }
 
+        let else_offset = self
+            .at(TokenKind::Else)
+            .then(|| self.current_range().start());
+        let mut else_colon_offset = None;
         let orelse = if self.eat(TokenKind::Else) {
+            else_colon_offset = Some(self.current_range().start());
             self.expect_and_recover(TokenKind::Colon, TokenSet::EMPTY);
 
             self.parse_body(Clause::Else)
@@ -1531,8 +2327,13 @@ impl<'src> Parser<'src> {
             vec![]
         };
 
+        let finally_offset = self
+            .at(TokenKind::Finally)
+            .then(|| self.current_range().start());
+        let mut finally_colon_offset = None;
         let finalbody = if self.eat(TokenKind::Finally) {
             has_finally = true;
+            finally_colon_offset = Some(self.current_range().start());
             self.expect_and_recover(TokenKind::Colon, TokenSet::EMPTY);
 
             self.parse_body(Clause::Finally)
@@ -1551,6 +2352,20 @@ impl<'src> Parser<'src> {
         }
 
         let range = self.node_range(try_start);
+        self.record_token_position(range, TokenKind::Try, try_offset);
+        self.record_token_position(range, TokenKind::Colon, try_colon_offset);
+        if let Some(else_offset) = else_offset {
+            self.record_token_position(range, TokenKind::Else, else_offset);
+        }
+        if let Some(else_colon_offset) = else_colon_offset {
+            self.record_token_position(range, TokenKind::Colon, else_colon_offset);
+        }
+        if let Some(finally_offset) = finally_offset {
+            self.record_token_position(range, TokenKind::Finally, finally_offset);
+        }
+        if let Some(finally_colon_offset) = finally_colon_offset {
+            self.record_token_position(range, TokenKind::Colon, finally_colon_offset);
+        }
 
         ast::StmtTry {
             body: try_body,
@@ -1569,12 +2384,15 @@ impl<'src> Parser<'src> {
 
         while self.at(TokenKind::At) {
             let decorator_start = self.node_start();
+            let at_offset = self.current_range().start();
             self.bump(TokenKind::At);
 
             let parsed_expr = self.parse_expr2();
+            let range = self.node_range(decorator_start);
+            self.record_token_position(range, TokenKind::At, at_offset);
             decorators.push(ast::Decorator {
                 expression: parsed_expr.expr,
-                range: self.node_range(decorator_start),
+                range,
             });
 
             self.expect(TokenKind::Newline);
@@ -1608,6 +2426,7 @@ impl<'src> Parser<'src> {
         decorator_list: Vec<ast::Decorator>,
         start_offset: TextSize,
     ) -> ast::StmtFunctionDef {
+        let def_offset = self.current_range().start();
         self.bump(TokenKind::Def);
         let name = self.parse_identifier();
         let type_params = if self.at(TokenKind::Lsqb) {
@@ -1639,21 +2458,32 @@ impl<'src> Parser<'src> {
 
         parameters.range = lpar_range.cover(rpar_range);
 
+        let arrow_offset = self
+            .at(TokenKind::Rarrow)
+            .then(|| self.current_range().start());
         let returns = if self.eat(TokenKind::Rarrow) {
-            let returns = self.parse_exprs();
+            let mut returns = self.parse_exprs();
             if !returns.is_parenthesized && matches!(returns.expr, Expr::Tuple(_)) {
-                self.add_error(
+                let range = returns.expr.range();
+                self.add_error_with_suggestion(
                     ParseErrorType::OtherError(
                         "multiple return types must be parenthesized".to_string(),
                     ),
-                    returns.expr.range(),
+                    range,
+                    Suggestion {
+                        range,
+                        replacement: format!("({})", self.src_text(range)),
+                        applicability: Applicability::MachineApplicable,
+                    },
                 );
+                recover_tuple_as_parenthesized(&mut returns.expr);
             }
             Some(Box::new(returns.expr))
         } else {
             None
         };
 
+        let colon_offset = self.current_range().start();
         self.expect_and_recover(
             TokenKind::Colon,
             SIMPLE_STMT_SET
@@ -1663,6 +2493,15 @@ impl<'src> Parser<'src> {
 
         let body = self.parse_body(Clause::FunctionDef);
 
+        let range = self.node_range(start_offset);
+        self.record_token_position(range, TokenKind::Def, def_offset);
+        self.record_token_position(range, TokenKind::Lpar, lpar_range.start());
+        self.record_token_position(range, TokenKind::Rpar, rpar_range.start());
+        if let Some(arrow_offset) = arrow_offset {
+            self.record_token_position(range, TokenKind::Rarrow, arrow_offset);
+        }
+        self.record_token_position(range, TokenKind::Colon, colon_offset);
+
         ast::StmtFunctionDef {
             name,
             type_params,
@@ -1671,7 +2510,7 @@ impl<'src> Parser<'src> {
             decorator_list,
             is_async: false,
             returns,
-            range: self.node_range(start_offset),
+            range,
         }
     }
 
@@ -1680,6 +2519,7 @@ impl<'src> Parser<'src> {
         decorator_list: Vec<ast::Decorator>,
         start_offset: TextSize,
     ) -> ast::StmtClassDef {
+        let class_offset = self.current_range().start();
         self.bump(TokenKind::Class);
 
         let name = self.parse_identifier();
@@ -1694,12 +2534,17 @@ impl<'src> Parser<'src> {
             None
         };
 
+        let colon_offset = self.current_range().start();
         self.expect_and_recover(TokenKind::Colon, TokenSet::EMPTY);
 
         let body = self.parse_body(Clause::Class);
 
+        let range = self.node_range(start_offset);
+        self.record_token_position(range, TokenKind::Class, class_offset);
+        self.record_token_position(range, TokenKind::Colon, colon_offset);
+
         ast::StmtClassDef {
-            range: self.node_range(start_offset),
+            range,
             decorator_list,
             name,
             type_params,
@@ -1734,13 +2579,17 @@ impl<'src> Parser<'src> {
             let mut target = self.parse_expr();
 
             if matches!(target.expr, Expr::BoolOp(_) | Expr::Compare(_)) {
-                // Should we make `target` an `Expr::Invalid` here?
+                let range = target.expr.range();
                 self.add_error(
                     ParseErrorType::OtherError(
                         "expression not allowed in `with` statement".to_string(),
                     ),
-                    target.expr.range(),
+                    range,
                 );
+                target.expr = Expr::Invalid(ast::ExprInvalid {
+                    value: self.src_text(range).into(),
+                    range,
+                });
             }
 
             helpers::set_expr_ctx(&mut target.expr, ExprContext::Store);
@@ -1777,95 +2626,50 @@ impl<'src> Parser<'src> {
         //
         // In the first example, the `item` contains a parenthesized expression,
         // while the second example is a parenthesized `WithItem`. This situation
-        // introduces ambiguity during parsing. When encountering an opening parenthesis
-        // `(,` the parser may initially assume it's parsing a parenthesized `WithItem`.
-        // However, this assumption doesn't hold for the first case, `(a) as A`, where
-        // `(a)` represents a parenthesized expression.
-        //
-        // To disambiguate, the following heuristic was created. First, assume we're
-        // parsing an expression, then we look for the following tokens:
-        //      i) `as` keyword outside parenthesis
-        //      ii) `,` outside or inside parenthesis
-        //      iii) `:=` inside an 1-level nested parenthesis
-        //      iv) `*` inside an 1-level nested parenthesis, representing a starred
-        //         expression
+        // introduces ambiguity during parsing: on seeing the opening parenthesis `(`
+        // the parser doesn't yet know whether it's about to parse a parenthesized
+        // `WithItem` list or a single parenthesized expression.
         //
-        // If we find case i we treat it as in case 1. For case ii, we only treat it as in
-        // case 1 if the comma is outside of parenthesis and we've seen an `Rpar` or `Lpar`
-        // before the comma.
-        // Cases iii and iv are special cases, when we find them, we treat it as in case 2.
-        // The reason for this is that the resulting AST node needs to be a tuple for cases
-        // iii and iv instead of multiple `WithItem`s. For example, `with (a, b := 0, c): ...`
-        // will be parsed as one `WithItem` containing a tuple, instead of three different `WithItem`s.
-        let mut treat_it_as_expr = true;
+        // Rather than hand-rolling a lookahead scan to disambiguate, speculatively
+        // parse the parenthesized form as a comma-separated list of `WithItem`s. If
+        // that doesn't cleanly reach a `)` followed by `:` (e.g. because it was
+        // actually a tuple, a starred expression, or a walrus assignment, as in
+        // `with (a, b := 0, c): ...`), rewind and reparse it as a single, possibly
+        // parenthesized, expression instead.
         if has_seen_lpar {
-            let mut index = 1;
-            let mut paren_nesting = 1;
-            let mut ignore_comma_check = false;
-            let mut has_seen_rpar = false;
-            let mut has_seen_colon_equal = false;
-            let mut has_seen_star = false;
-            let mut prev_token = self.current_kind();
-            loop {
-                let (kind, _) = self.peek_nth(index);
-                match kind {
-                    TokenKind::Lpar => {
-                        paren_nesting += 1;
-                    }
-                    TokenKind::Rpar => {
-                        paren_nesting -= 1;
-                        has_seen_rpar = true;
-                    }
-                    // Check for `:=` inside an 1-level nested parens, e.g. `with (a, b := c): ...`
-                    TokenKind::ColonEqual if paren_nesting == 1 => {
-                        treat_it_as_expr = true;
-                        ignore_comma_check = true;
-                        has_seen_colon_equal = true;
-                    }
-                    // Check for starred expressions inside an 1-level nested parens,
-                    // e.g. `with (a, *b): ...`
-                    TokenKind::Star if paren_nesting == 1 && !LITERAL_SET.contains(prev_token) => {
-                        treat_it_as_expr = true;
-                        ignore_comma_check = true;
-                        has_seen_star = true;
-                    }
-                    // Check for `as` keyword outside parens
-                    TokenKind::As => {
-                        treat_it_as_expr = paren_nesting == 0;
-                        ignore_comma_check = true;
-                    }
-                    TokenKind::Comma if !ignore_comma_check => {
-                        // If the comma is outside of parens, treat it as an expression
-                        // if we've seen `(` and `)`.
-                        if paren_nesting == 0 {
-                            treat_it_as_expr = has_seen_lpar && has_seen_rpar;
-                        } else if !has_seen_star && !has_seen_colon_equal {
-                            treat_it_as_expr = false;
-                        }
-                    }
-                    TokenKind::Colon | TokenKind::Newline => break,
-                    _ => {}
-                }
+            let checkpoint = self.checkpoint();
+
+            self.bump(TokenKind::Lpar);
+            let mut speculative_items = vec![];
+            self.parse_separated(
+                true,
+                TokenKind::Comma,
+                [TokenKind::Rpar].as_slice(),
+                SeparatorRecovery::Strict,
+                |parser| {
+                    let item = parser.parse_with_item();
+                    let range = item.range;
+                    speculative_items.push(item);
+                    range
+                },
+            );
 
-                index += 1;
-                prev_token = kind;
+            if self.errors.len() == checkpoint.errors_len
+                && self.eat(TokenKind::Rpar)
+                && self.at(TokenKind::Colon)
+            {
+                return speculative_items;
             }
-        }
 
-        if !treat_it_as_expr && has_seen_lpar {
-            self.bump(TokenKind::Lpar);
+            self.rewind(checkpoint);
         }
 
-        let ending = if has_seen_lpar && treat_it_as_expr {
-            [TokenKind::Colon]
-        } else {
-            [TokenKind::Rpar]
-        };
         self.parse_separated(
             // Only allow a trailing delimiter if we've seen a `(`.
             has_seen_lpar,
             TokenKind::Comma,
-            ending.as_slice(),
+            [TokenKind::Colon].as_slice(),
+            SeparatorRecovery::Strict,
             |parser| {
                 let item = parser.parse_with_item();
                 let range = item.range;
@@ -1886,8 +2690,7 @@ impl<'src> Parser<'src> {
         // The exception is when `WithItem` is an `()` (empty tuple).
         if items.len() == 1 {
             let with_item = items.last_mut().unwrap();
-            if treat_it_as_expr
-                && with_item.optional_vars.is_none()
+            if with_item.optional_vars.is_none()
                 && self.last_ctx.contains(ParserCtxFlags::PARENTHESIZED_EXPR)
                 && !matches!(with_item.context_expr, Expr::Tuple(_))
             {
@@ -1895,10 +2698,6 @@ impl<'src> Parser<'src> {
             }
         }
 
-        if !treat_it_as_expr && has_seen_lpar {
-            self.expect_and_recover(TokenKind::Rpar, TokenSet::new(&[TokenKind::Colon]));
-        }
-
         items
     }
 
@@ -1969,13 +2768,20 @@ impl<'src> Parser<'src> {
         helpers::set_expr_ctx(&mut target.expr, ExprContext::Store);
 
         let simple = matches!(target.expr, Expr::Name(_)) && !target.is_parenthesized;
-        let annotation = self.parse_exprs();
+        let mut annotation = self.parse_exprs();
 
         if matches!(annotation.expr, Expr::Tuple(_)) && !annotation.is_parenthesized {
-            self.add_error(
+            let range = annotation.expr.range();
+            self.add_error_with_suggestion(
                 ParseErrorType::OtherError("annotation cannot be unparenthesized".into()),
-                annotation.expr.range(),
+                range,
+                Suggestion {
+                    range,
+                    replacement: format!("({})", self.src_text(range)),
+                    applicability: Applicability::MachineApplicable,
+                },
             );
+            recover_tuple_as_parenthesized(&mut annotation.expr);
         }
 
         let value = if self.eat(TokenKind::Equal) {
@@ -2103,7 +2909,9 @@ impl<'src> Parser<'src> {
             TokenKind::From => Stmt::ImportFrom(self.parse_import_from_stmt()),
             TokenKind::Continue => Stmt::Continue(self.parse_continue_stmt()),
             TokenKind::Nonlocal => Stmt::Nonlocal(self.parse_nonlocal_stmt()),
-            TokenKind::Type => Stmt::TypeAlias(self.parse_type_alias_stmt()),
+            TokenKind::Type if !self.is_soft_keyword_at(TokenKind::Type) => {
+                Stmt::TypeAlias(self.parse_type_alias_stmt())
+            }
             TokenKind::EscapeCommand if self.mode == Mode::Ipython => {
                 Stmt::IpyEscapeCommand(self.parse_ipython_escape_command_stmt())
             }
@@ -2194,19 +3002,52 @@ impl<'src> Parser<'src> {
             true,
             TokenKind::Comma,
             [TokenKind::Newline].as_slice(),
+            SeparatorRecovery::Strict,
             |parser| {
-                let mut target = parser.parse_expr();
+                let mut target = parser.with_restrictions(
+                    Restrictions::STARRED_EXPRESSION_FORBIDDEN,
+                    Parser::parse_expr,
+                );
                 helpers::set_expr_ctx(&mut target.expr, ExprContext::Del);
 
-                if matches!(target.expr, Expr::BoolOp(_) | Expr::Compare(_)) {
-                    // Should we make `target` an `Expr::Invalid` here?
+                if let Expr::BoolOp(ast::ExprBoolOp { values, .. }) = &target.expr {
+                    // `del a and b` is almost always a typo for deleting two separate
+                    // targets, so suggest the comma-separated form.
+                    let range = target.expr.range();
+                    let replacement = values
+                        .iter()
+                        .map(|value| parser.src_text(value))
+                        .collect::<Vec<_>>()
+                        .join(", ");
+                    parser.add_error_with_suggestion(
+                        ParseErrorType::OtherError(format!(
+                            "`{}` not allowed in `del` statement",
+                            parser.src_text(range)
+                        )),
+                        range,
+                        Suggestion {
+                            range,
+                            replacement,
+                            applicability: Applicability::MaybeIncorrect,
+                        },
+                    );
+                    target.expr = Expr::Invalid(ast::ExprInvalid {
+                        value: parser.src_text(range).into(),
+                        range,
+                    });
+                } else if matches!(target.expr, Expr::Compare(_)) {
+                    let range = target.expr.range();
                     parser.add_error(
                         ParseErrorType::OtherError(format!(
                             "`{}` not allowed in `del` statement",
-                            parser.src_text(&target.expr)
+                            parser.src_text(range)
                         )),
-                        &target.expr,
+                        range,
                     );
+                    target.expr = Expr::Invalid(ast::ExprInvalid {
+                        value: parser.src_text(range).into(),
+                        range,
+                    });
                 }
                 targets.push(target.expr);
                 TextRange::default()
@@ -2249,6 +3090,7 @@ impl<'src> Parser<'src> {
             false,
             TokenKind::Comma,
             [TokenKind::Newline].as_slice(),
+            SeparatorRecovery::Strict,
             |parser| {
                 let ident = parser.parse_identifier();
                 let range = ident.range;
@@ -2273,6 +3115,7 @@ impl<'src> Parser<'src> {
             false,
             TokenKind::Comma,
             [TokenKind::Newline].as_slice(),
+            SeparatorRecovery::Strict,
             |parser| {
                 let ident = parser.parse_identifier();
                 let range = ident.range;
@@ -2314,11 +3157,16 @@ impl<'src> Parser<'src> {
 
             if let Expr::Tuple(node) = &exc.expr {
                 if !exc.is_parenthesized {
-                    self.add_error(
+                    self.add_error_with_suggestion(
                         ParseErrorType::OtherError(
                             "unparenthesized tuple not allowed in `raise` statement".to_string(),
                         ),
                         node.range,
+                        Suggestion {
+                            range: node.range,
+                            replacement: format!("({})", self.src_text(node.range)),
+                            applicability: Applicability::MachineApplicable,
+                        },
                     );
                 }
             }
@@ -2331,12 +3179,17 @@ impl<'src> Parser<'src> {
 
             if let Expr::Tuple(node) = &cause.expr {
                 if !cause.is_parenthesized {
-                    self.add_error(
+                    self.add_error_with_suggestion(
                         ParseErrorType::OtherError(
                             "unparenthesized tuple not allowed in `raise from` statement"
                                 .to_string(),
                         ),
                         node.range,
+                        Suggestion {
+                            range: node.range,
+                            replacement: format!("({})", self.src_text(node.range)),
+                            applicability: Applicability::MachineApplicable,
+                        },
                     );
                 }
             }
@@ -2398,6 +3251,7 @@ impl<'src> Parser<'src> {
             TokenKind::Lsqb,
             TokenKind::Comma,
             TokenKind::Rsqb,
+            SeparatorRecovery::Strict,
             |parser| {
                 type_params.push(parser.parse_type_param());
             },
@@ -2499,6 +3353,7 @@ impl<'src> Parser<'src> {
             false,
             TokenKind::Comma,
             [TokenKind::Newline].as_slice(),
+            SeparatorRecovery::Strict,
             |parser| {
                 let alias = parser.parse_alias();
                 let range = alias.range;
@@ -2536,6 +3391,8 @@ impl<'src> Parser<'src> {
         };
 
         if level == 0 && module.is_none() {
+            // No suggestion here: the module name is simply absent, and there's
+            // nothing in the surrounding tokens to infer a replacement from.
             let range = self.current_range();
             self.add_error(
                 ParseErrorType::OtherError("missing module name".to_string()),
@@ -2552,6 +3409,7 @@ impl<'src> Parser<'src> {
                 TokenKind::Lpar,
                 TokenKind::Comma,
                 TokenKind::Rpar,
+                SeparatorRecovery::Strict,
                 |parser| {
                     names.push(parser.parse_alias());
                 },
@@ -2561,6 +3419,7 @@ impl<'src> Parser<'src> {
                 false,
                 TokenKind::Comma,
                 [TokenKind::Newline].as_slice(),
+                SeparatorRecovery::Strict,
                 |parser| {
                     let alias = parser.parse_alias();
                     let range = alias.range;
@@ -2606,12 +3465,33 @@ impl<'src> Parser<'src> {
         }
     }
 
+    /// Whether the parser is positioned at `else` immediately followed by
+    /// `if`, the C/JavaScript spelling of `elif`.
+    fn at_else_if(&mut self) -> bool {
+        self.at(TokenKind::Else) && self.peek_nth(1).0 == TokenKind::If
+    }
+
     fn parse_elif_else_clauses(&mut self) -> Vec<ast::ElifElseClause> {
         let mut elif_else_stmts = vec![];
 
-        while self.at(TokenKind::Elif) {
+        while self.at(TokenKind::Elif) || self.at_else_if() {
             let elif_start = self.node_start();
-            self.bump(TokenKind::Elif);
+            if self.at_else_if() {
+                let range = self.current_range().cover(self.peek_nth(1).1);
+                self.add_error_with_suggestion(
+                    ParseErrorType::OtherError("use `elif` instead of `else if`".to_string()),
+                    range,
+                    Suggestion {
+                        range,
+                        replacement: "elif".to_string(),
+                        applicability: Applicability::MachineApplicable,
+                    },
+                );
+                self.bump(TokenKind::Else);
+                self.bump(TokenKind::If);
+            } else {
+                self.bump(TokenKind::Elif);
+            }
 
             let test = self.parse_expr_with_recovery(
                 Parser::parse_expr2,
@@ -2647,6 +3527,25 @@ impl<'src> Parser<'src> {
     }
 
     fn parse_body(&mut self, parent_clause: Clause) -> Vec<Stmt> {
+        let _depth_guard = self.recursion_guard();
+        if self.recursion_depth_exceeded() {
+            let (error_range, skipped_range) =
+                self.recover_from_recursion_limit(TokenSet::new(&[TokenKind::Dedent]));
+            let range = error_range.cover(skipped_range);
+            self.eat(TokenKind::Dedent);
+            return vec![Stmt::Expr(ast::StmtExpr {
+                value: Box::new(Expr::Invalid(ast::ExprInvalid {
+                    value: self.src_text(range).into(),
+                    range,
+                })),
+                range,
+            })];
+        }
+
+        self.with_stack_headroom(|parser| parser.parse_body_impl(parent_clause))
+    }
+
+    fn parse_body_impl(&mut self, parent_clause: Clause) -> Vec<Stmt> {
         let mut stmts = vec![];
 
         // Check if we are currently at a simple statement
@@ -2666,18 +3565,34 @@ impl<'src> Parser<'src> {
                     continue;
                 }
 
-                stmts.push(self.parse_statement());
+                self.parse_statement_with_recovery(&mut stmts);
             }
 
             self.eat(TokenKind::Dedent);
         } else {
-            let range = self.current_range();
-            self.add_error(
-                ParseErrorType::OtherError(format!(
-                    "expected an indented block after {parent_clause}"
-                )),
-                range,
-            );
+            // The line wasn't indented, but it might still be a single stray
+            // statement at the header's level with the indentation simply
+            // forgotten (`if x:\ny = 1\n`). Checkpoint, parse one statement
+            // speculatively under that relaxed reading, and keep it if it
+            // produced something real; otherwise rewind -- discarding both the
+            // consumed tokens and any errors the failed attempt pushed -- and
+            // fall back to the plain "expected an indented block" diagnostic.
+            let checkpoint = self.checkpoint();
+            self.parse_statement_with_recovery(&mut stmts);
+            let parsed_something_real = stmts.iter().any(|stmt| {
+                !matches!(stmt, Stmt::Expr(expr) if matches!(expr.value.as_ref(), Expr::Invalid(_)))
+            });
+            if !parsed_something_real {
+                self.rewind(checkpoint);
+                stmts.clear();
+                let range = self.current_range();
+                self.add_error(
+                    ParseErrorType::OtherError(format!(
+                        "expected an indented block after {parent_clause}"
+                    )),
+                    range,
+                );
+            }
         }
 
         stmts
@@ -2702,6 +3617,22 @@ impl<'src> Parser<'src> {
     /// NOTE: If you have expressions separated by commas and want to parse them individually,
     /// instead of a tuple, use this function!
     fn parse_expr(&mut self) -> ParsedExpr {
+        let _depth_guard = self.recursion_guard();
+        if self.recursion_depth_exceeded() {
+            let (error_range, skipped_range) = self.recover_from_recursion_limit(TokenSet::EMPTY);
+            let range = error_range.cover(skipped_range);
+            return Expr::Invalid(ast::ExprInvalid {
+                value: self.src_text(range).into(),
+                range,
+            })
+            .into();
+        }
+
+        self.with_stack_headroom(Self::parse_expr_impl)
+    }
+
+    fn parse_expr_impl(&mut self) -> ParsedExpr {
+
         let start = self.node_start();
         let parsed_expr = self.parse_expr_simple();
 
@@ -2746,12 +3677,35 @@ impl<'src> Parser<'src> {
         if self.at_expr() {
             parse_func(self)
         } else {
+            let recover_set = recover_set.into();
+
+            // `at_expr` only looks one token ahead, so it can't tell a single
+            // stray token (an extra comma, a misplaced keyword) from a
+            // genuinely missing expression. Checkpoint and peek past it
+            // before giving up on the whole expression.
+            if !self.at_ts(NEWLINE_EOF_SET.union(recover_set)) {
+                let checkpoint = self.checkpoint();
+                let unexpected_range = self.current_range();
+                self.next_token();
+                if self.at_expr() {
+                    self.add_error(
+                        ParseErrorType::OtherError(format!(
+                            "unexpected token `{}`",
+                            self.src_text(unexpected_range)
+                        )),
+                        unexpected_range,
+                    );
+                    return parse_func(self);
+                }
+                self.rewind(checkpoint);
+            }
+
             let start = self.node_start();
             self.add_error(
                 ParseErrorType::OtherError(error_msg.to_string()),
                 self.current_range(),
             );
-            self.skip_until(NEWLINE_EOF_SET.union(recover_set.into()));
+            self.skip_until(NEWLINE_EOF_SET.union(recover_set));
 
             // FIXME(micha): I don't think we should include the entire range, or the range at all because it risks including trivia
             let range = self.node_range(start);
@@ -2798,16 +3752,80 @@ impl<'src> Parser<'src> {
         }
     }
 
+    /// Whether the parser is positioned at a trailing `++` or `--`, the C/JavaScript
+    /// increment/decrement operators, immediately following an atom. A doubled
+    /// `+`/`-` followed by the start of an expression is valid Python (`a - -b` is a
+    /// binary minus whose right operand is a unary minus), so the pair only counts
+    /// as this mistake when nothing that can start an expression follows it.
+    fn at_foreign_increment_or_decrement(&mut self) -> bool {
+        matches!(self.current_kind(), TokenKind::Plus | TokenKind::Minus)
+            && self.peek_nth(1).0 == self.current_kind()
+            && !EXPR_SET.contains(self.peek_nth(2).0)
+    }
+
+    /// Whether the parser is positioned at `&&` or `||`, the C/JavaScript
+    /// spelling of `and`/`or`. A lone `&`/`|` is a valid bitwise operator, but
+    /// neither can start an expression, so seeing one immediately followed by
+    /// another is never valid Python and safe to treat as this mistake.
+    fn foreign_logical_op(&mut self) -> Option<(BoolOp, TextRange)> {
+        let bool_op = match self.current_kind() {
+            TokenKind::Amper => BoolOp::And,
+            TokenKind::Vbar => BoolOp::Or,
+            _ => return None,
+        };
+
+        if self.peek_nth(1).0 != self.current_kind() {
+            return None;
+        }
+
+        Some((bool_op, self.current_range().cover(self.peek_nth(1).1)))
+    }
+
     /// Parses expression with binding power of at least bp.
     ///
     /// Uses the Pratt parser algorithm.
     /// See <https://matklad.github.io/2020/04/13/simple-but-powerful-pratt-parsing.html>
     // FIXME(micha): Introduce precedence enum instead of passing cryptic u8 values.
     fn parse_expression_with_precedence(&mut self, bp: u8) -> ParsedExpr {
+        let _depth_guard = self.recursion_guard();
+        if self.recursion_depth_exceeded() {
+            let (error_range, skipped_range) = self.recover_from_recursion_limit(TokenSet::EMPTY);
+            let range = error_range.cover(skipped_range);
+            return Expr::Invalid(ast::ExprInvalid {
+                value: self.src_text(range).into(),
+                range,
+            })
+            .into();
+        }
+
+        self.with_stack_headroom(move |parser| parser.parse_expression_with_precedence_impl(bp))
+    }
+
+    fn parse_expression_with_precedence_impl(&mut self, bp: u8) -> ParsedExpr {
         let start = self.node_start();
         let mut lhs = self.parse_lhs();
 
         loop {
+            if let Some((bool_op, _)) = self.foreign_logical_op() {
+                let op_bp = match bool_op {
+                    BoolOp::And => 5,
+                    BoolOp::Or => 4,
+                };
+                if op_bp < bp {
+                    break;
+                }
+
+                // Left-associative, same as the real `and`/`or` call site below.
+                lhs = Expr::BoolOp(self.parse_foreign_bool_op_expr(
+                    lhs.expr,
+                    start,
+                    bool_op,
+                    op_bp + 1,
+                ))
+                .into();
+                continue;
+            }
+
             let (op_bp, op, associativity) = self.current_op();
             if op_bp < bp {
                 break;
@@ -2866,12 +3884,30 @@ impl<'src> Parser<'src> {
     }
 
     fn parse_lhs(&mut self) -> ParsedExpr {
+        let _depth_guard = self.recursion_guard();
+        if self.recursion_depth_exceeded() {
+            let (error_range, skipped_range) = self.recover_from_recursion_limit(TokenSet::EMPTY);
+            let range = error_range.cover(skipped_range);
+            return Expr::Invalid(ast::ExprInvalid {
+                value: self.src_text(range).into(),
+                range,
+            })
+            .into();
+        }
+
+        self.with_stack_headroom(Self::parse_lhs_impl)
+    }
+
+    fn parse_lhs_impl(&mut self) -> ParsedExpr {
         let start = self.node_start();
         let token = self.next_token();
         let mut lhs = match token.0 {
             token @ (Tok::Plus | Tok::Minus | Tok::Not | Tok::Tilde) => {
                 Expr::UnaryOp(self.parse_unary_expr(token, start)).into()
             }
+            Tok::Exclamation => {
+                Expr::UnaryOp(self.parse_foreign_not_expr(token.1, start)).into()
+            }
             Tok::Star => Expr::Starred(self.parse_starred_expr(start)).into(),
             Tok::Await => Expr::Await(self.parse_await_expr(start)).into(),
             Tok::Lambda => Expr::Lambda(self.parse_lambda_expr(start)).into(),
@@ -2888,19 +3924,30 @@ impl<'src> Parser<'src> {
 
     fn parse_identifier(&mut self) -> ast::Identifier {
         let range = self.current_range();
-        if self.current_kind() == TokenKind::Name {
-            let (Tok::Name { name }, _) = self.next_token() else {
-                unreachable!();
-            };
-            ast::Identifier { id: name, range }
-        } else {
-            self.add_error(
-                ParseErrorType::OtherError("expecting an identifier".into()),
-                range,
-            );
-            ast::Identifier {
-                id: String::new(),
-                range,
+        match self.current_kind() {
+            TokenKind::Name => {
+                let (Tok::Name { name }, _) = self.next_token() else {
+                    unreachable!();
+                };
+                ast::Identifier { id: name, range }
+            }
+            // `match`, `case`, and `type` are soft keywords and remain valid
+            // identifiers wherever an identifier (rather than a statement start) is
+            // expected, e.g. as a parameter or `as`-bound name.
+            TokenKind::Match | TokenKind::Case | TokenKind::Type => {
+                let id = self.src_text(range).to_string();
+                self.next_token();
+                ast::Identifier { id, range }
+            }
+            _ => {
+                self.add_error(
+                    ParseErrorType::OtherError("expecting an identifier".into()),
+                    range,
+                );
+                ast::Identifier {
+                    id: String::new(),
+                    range,
+                }
             }
         }
     }
@@ -2938,6 +3985,14 @@ impl<'src> Parser<'src> {
                 ctx: ExprContext::Load,
                 range: self.node_range(start),
             }),
+            // `match`, `case`, and `type` are soft keywords: outside the handful of
+            // statement-start positions that dispatch on them directly (guarded by
+            // `is_soft_keyword_at`), they're ordinary names.
+            Tok::Match | Tok::Case | Tok::Type => Expr::Name(ast::ExprName {
+                id: self.src_text(token_range).to_string(),
+                ctx: ExprContext::Load,
+                range: self.node_range(start),
+            }),
             Tok::IpyEscapeCommand { value, kind } if self.mode == Mode::Ipython => {
                 Expr::IpyEscapeCommand(ast::ExprIpyEscapeCommand {
                     range: self.node_range(start),
@@ -2997,11 +4052,28 @@ impl<'src> Parser<'src> {
     }
 
     fn parse_postfix_expr(&mut self, mut lhs: Expr, start: TextSize) -> Expr {
+        let _depth_guard = self.recursion_guard();
+        if self.recursion_depth_exceeded() {
+            let (error_range, skipped_range) = self.recover_from_recursion_limit(TokenSet::EMPTY);
+            let range = error_range.cover(skipped_range);
+            return Expr::Invalid(ast::ExprInvalid {
+                value: self.src_text(range).into(),
+                range,
+            });
+        }
+
+        self.with_stack_headroom(move |parser| parser.parse_postfix_expr_impl(lhs, start))
+    }
+
+    fn parse_postfix_expr_impl(&mut self, mut lhs: Expr, start: TextSize) -> Expr {
         loop {
             lhs = match self.current_kind() {
                 TokenKind::Lpar => Expr::Call(self.parse_call_expr(lhs, start)),
                 TokenKind::Lsqb => Expr::Subscript(self.parse_subscript_expr(lhs, start)),
                 TokenKind::Dot => Expr::Attribute(self.parse_attribute_expr(lhs, start)),
+                _ if self.at_foreign_increment_or_decrement() => {
+                    self.parse_foreign_increment_or_decrement_expr(lhs, start)
+                }
                 _ => break,
             };
         }
@@ -3009,6 +4081,34 @@ impl<'src> Parser<'src> {
         lhs
     }
 
+    /// Recovers `x++`/`x--` (the C/JavaScript increment/decrement operators) as
+    /// if the user had written the augmented assignment `x += 1`/`x -= 1`: report
+    /// the mistake with a suggestion, consume the doubled operator, and return
+    /// `lhs` unchanged so parsing proceeds with a plausible node rather than
+    /// falling into generic recovery.
+    fn parse_foreign_increment_or_decrement_expr(&mut self, lhs: Expr, start: TextSize) -> Expr {
+        let (spelling, replacement) = match self.current_kind() {
+            TokenKind::Plus => ("++", "+= 1"),
+            _ => ("--", "-= 1"),
+        };
+        let op_range = self.current_range().cover(self.peek_nth(1).1);
+        let lhs_range = self.node_range(start);
+        self.add_error_with_suggestion(
+            ParseErrorType::OtherError(format!(
+                "`{spelling}` is not a Python operator, use `{replacement}`"
+            )),
+            op_range,
+            Suggestion {
+                range: lhs_range.cover(op_range),
+                replacement: format!("{} {replacement}", self.src_text(lhs_range)),
+                applicability: Applicability::MaybeIncorrect,
+            },
+        );
+        self.next_token();
+        self.next_token();
+        lhs
+    }
+
     fn parse_call_expr(&mut self, lhs: Expr, start: TextSize) -> ast::ExprCall {
         assert_eq!(self.current_kind(), TokenKind::Lpar);
         let arguments = self.parse_arguments();
@@ -3035,6 +4135,7 @@ impl<'src> Parser<'src> {
             TokenKind::Lpar,
             TokenKind::Comma,
             TokenKind::Rpar,
+            SeparatorRecovery::Recovering,
             |parser| {
                 let argument_start = parser.node_start();
                 if parser.at(TokenKind::DoubleStar) {
@@ -3054,12 +4155,42 @@ impl<'src> Parser<'src> {
 
                     match parser.current_kind() {
                         TokenKind::Async | TokenKind::For => {
-                            parsed_expr = Expr::GeneratorExp(parser.parse_generator_expr(
-                                parsed_expr.expr,
-                                start,
-                                false,
-                            ))
-                            .into();
+                            if matches!(parsed_expr.expr, Expr::Starred(_)) {
+                                // `f(*x for x in y)` is a `SyntaxError` in CPython too:
+                                // iterable unpacking can't be the element of a
+                                // comprehension. Consume the dangling `for`/`async for`
+                                // clause here rather than leaving it for
+                                // `parse_delimited`'s separator recovery, which would
+                                // otherwise report a second, spurious diagnostic for
+                                // the same mistake.
+                                //
+                                // This used to rewind to a `checkpoint()` taken before
+                                // `*x` and reparse it, but that bought nothing: `*x`
+                                // itself was already parsed correctly, there's nothing
+                                // wrong with it to undo, and rewinding still left the
+                                // `for`/`async for` clause dangling for `parse_delimited`
+                                // to stumble over -- the redundant reparse was pure
+                                // overhead for the same spurious second diagnostic this
+                                // comment is about avoiding. Consuming the generator
+                                // clause directly, without rewinding anything, is both
+                                // simpler and actually fixes the double-diagnostic.
+                                let range = parsed_expr.expr.range();
+                                parser.add_error(
+                                    ParseErrorType::OtherError(
+                                        "iterable unpacking cannot be used in a comprehension"
+                                            .to_string(),
+                                    ),
+                                    range,
+                                );
+                                parser.parse_generators();
+                            } else {
+                                parsed_expr = Expr::GeneratorExp(parser.parse_generator_expr(
+                                    parsed_expr.expr,
+                                    start,
+                                    false,
+                                ))
+                                .into();
+                            }
                         }
                         _ => {}
                     }
@@ -3079,16 +4210,25 @@ impl<'src> Parser<'src> {
                                 range: ident_expr.range,
                             }
                         } else {
-                            // FIXME(micha): This recovery looks fishy, it drops the parsed expression.
+                            // `ast::Keyword` only has room for an `Identifier`, and an
+                            // `Identifier` is only ever supposed to hold a valid Python
+                            // identifier -- but the struct itself doesn't enforce that,
+                            // it's just a `String` and a range. Stuff the original
+                            // (non-identifier) source text in there verbatim rather than
+                            // discarding it, so fix-up tooling and the formatter can
+                            // still recover what was actually written, even though it
+                            // isn't a valid identifier.
+                            let range = parsed_expr.expr.range();
+                            let text = parser.src_text(range);
                             parser.add_error(
-                                ParseErrorType::OtherError(
-                                    "cannot be used as a keyword argument!".to_string(),
-                                ),
-                                parsed_expr.expr.range(),
+                                ParseErrorType::OtherError(format!(
+                                    "`{text}` cannot be used as a keyword argument name, only identifiers are allowed"
+                                )),
+                                range,
                             );
                             ast::Identifier {
-                                id: String::new(),
-                                range: parsed_expr.expr.range(),
+                                id: text.to_string(),
+                                range,
                             }
                         };
 
@@ -3103,10 +4243,33 @@ impl<'src> Parser<'src> {
                         if has_seen_kw_arg
                             && !(has_seen_kw_unpack || matches!(parsed_expr.expr, Expr::Starred(_)))
                         {
-                            parser.add_error(
-                                ParseErrorType::PositionalArgumentError,
-                                parsed_expr.expr.range(),
-                            );
+                            let arg_range = parsed_expr.expr.range();
+                            // Moving the argument before the keywords isn't expressible as
+                            // a single-span replacement, so suggest it as two edits: drop
+                            // it from here, and insert it (plus a trailing comma) right
+                            // before the first keyword argument. `MaybeIncorrect` because
+                            // this changes the argument order, which could matter if any
+                            // of the expressions being moved past have side effects.
+                            let insert_at = keywords
+                                .first()
+                                .map_or(arg_range.start(), |first_kw| first_kw.range.start());
+                            let insert_pos = TextRange::empty(insert_at);
+                            parser.errors.push(ParseError {
+                                error: ParseErrorType::PositionalArgumentError,
+                                location: arg_range,
+                                suggestions: vec![
+                                    Suggestion {
+                                        range: insert_pos,
+                                        replacement: format!("{}, ", parser.src_text(arg_range)),
+                                        applicability: Applicability::MaybeIncorrect,
+                                    },
+                                    Suggestion {
+                                        range: arg_range,
+                                        replacement: String::new(),
+                                        applicability: Applicability::MaybeIncorrect,
+                                    },
+                                ],
+                            });
                         }
                         args.push(parsed_expr.expr);
                     }
@@ -3141,7 +4304,15 @@ impl<'src> Parser<'src> {
             self.expect_and_recover(TokenKind::Rsqb, TokenSet::EMPTY);
 
             let range = self.node_range(start);
-            self.add_error(ParseErrorType::EmptySlice, range);
+            self.add_error_with_suggestion(
+                ParseErrorType::EmptySlice,
+                range,
+                Suggestion {
+                    range: slice_range,
+                    replacement: ":".to_string(),
+                    applicability: Applicability::MaybeIncorrect,
+                },
+            );
             return ast::ExprSubscript {
                 value: Box::new(value),
                 slice: Box::new(Expr::Invalid(ast::ExprInvalid {
@@ -3162,6 +4333,7 @@ impl<'src> Parser<'src> {
                 true,
                 TokenKind::Comma,
                 TokenSet::new(&[TokenKind::Rsqb]),
+                SeparatorRecovery::Recovering,
                 |parser| {
                     let slice = parser.parse_slice();
                     slices.push(slice);
@@ -3196,6 +4368,15 @@ impl<'src> Parser<'src> {
     fn parse_slice(&mut self) -> Expr {
         let start = self.node_start();
 
+        // Deciding slice-vs-plain-subscript here doesn't need `checkpoint`/
+        // `rewind`: both interpretations start by parsing the same `lower`
+        // expression, and whichever one it turns out to be, that parse is kept
+        // as-is -- there's nothing to roll back and retry differently. A named
+        // expression (`x := 1`) is the one case CPython's own grammar treats
+        // specially (it's only valid as the *entire* subscript, never as a
+        // slice bound), so bailing out to the plain-expression interpretation
+        // there, leaving any trailing `:upper` unconsumed for the caller to
+        // report, is the correct diagnostic, not a bug needing a rewind.
         let lower = if self.at_expr() {
             let lower = self.parse_expr2();
 
@@ -3254,6 +4435,28 @@ impl<'src> Parser<'src> {
         }
     }
 
+    /// Recovers `!x` (the C/JavaScript spelling of logical negation) as `not x`:
+    /// `!` can't start an expression on its own (it's only ever part of `!=`,
+    /// already a distinct token), so seeing it where one was expected is safe to
+    /// treat as this mistake rather than a generic unexpected-token error.
+    fn parse_foreign_not_expr(&mut self, bang_range: TextRange, start: TextSize) -> ast::ExprUnaryOp {
+        self.add_error_with_suggestion(
+            ParseErrorType::OtherError("`!` is not a Python operator, use `not`".to_string()),
+            bang_range,
+            Suggestion {
+                range: bang_range,
+                replacement: "not".to_string(),
+                applicability: Applicability::MachineApplicable,
+            },
+        );
+        let rhs = self.parse_expression_with_precedence(6);
+        ast::ExprUnaryOp {
+            op: UnaryOp::Not,
+            operand: Box::new(rhs.expr),
+            range: self.node_range(start),
+        }
+    }
+
     fn parse_attribute_expr(&mut self, value: Expr, start: TextSize) -> ast::ExprAttribute {
         self.bump(TokenKind::Dot);
 
@@ -3296,6 +4499,71 @@ impl<'src> Parser<'src> {
         }
     }
 
+    /// Same flattening as [`Parser::parse_bool_op_expr`], but for a run of `&&`/`||`,
+    /// the C/JavaScript spelling of `and`/`or` (see [`Parser::foreign_logical_op`]).
+    /// Each occurrence gets its own suggestion, and consecutive occurrences of the
+    /// same foreign operator are folded into a single `BoolOp` instead of a
+    /// right-nested chain.
+    fn parse_foreign_bool_op_expr(
+        &mut self,
+        lhs: Expr,
+        start: TextSize,
+        bool_op: BoolOp,
+        op_bp: u8,
+    ) -> ast::ExprBoolOp {
+        let replacement = match bool_op {
+            BoolOp::And => "and",
+            BoolOp::Or => "or",
+        };
+        let mut values = vec![lhs];
+
+        loop {
+            let op_range = self.current_range().cover(self.peek_nth(1).1);
+            self.add_error_with_suggestion(
+                ParseErrorType::OtherError(format!(
+                    "`{}` is not a Python operator, use `{replacement}`",
+                    self.src_text(op_range)
+                )),
+                op_range,
+                Suggestion {
+                    range: op_range,
+                    replacement: replacement.to_string(),
+                    applicability: Applicability::MachineApplicable,
+                },
+            );
+            self.next_token();
+            self.next_token();
+
+            let parsed_expr = if self.at_expr() {
+                self.parse_expression_with_precedence(op_bp)
+            } else {
+                let rhs_range = self.current_range();
+                self.add_error(
+                    ParseErrorType::OtherError("expecting an expression after operand".into()),
+                    rhs_range,
+                );
+
+                Expr::Invalid(ast::ExprInvalid {
+                    value: self.src_text(rhs_range).into(),
+                    range: rhs_range,
+                })
+                .into()
+            };
+            values.push(parsed_expr.expr);
+
+            match self.foreign_logical_op() {
+                Some((next_bool_op, _)) if next_bool_op == bool_op => {}
+                _ => break,
+            }
+        }
+
+        ast::ExprBoolOp {
+            values,
+            op: bool_op,
+            range: self.node_range(start),
+        }
+    }
+
     fn parse_compare_op_expr(
         &mut self,
         lhs: Expr,
@@ -3522,7 +4790,7 @@ impl<'src> Parser<'src> {
                 _ => {
                     let (tok, range) = self.next_token();
                     self.add_error(
-                        ParseErrorType::OtherError(format!("f-string: unexpected token `{tok:?}`")),
+                        ParseErrorType::OtherError(format!("f-string: unexpected token `{tok}`")),
                         range,
                     );
                     continue;
@@ -3550,9 +4818,15 @@ impl<'src> Parser<'src> {
             "f-string: expecting expression",
         );
         if !value.is_parenthesized && matches!(value.expr, Expr::Lambda(_)) {
-            self.add_error(
+            let lambda_range = value.expr.range();
+            self.add_error_with_suggestion(
                 ParseErrorType::FStringError(FStringErrorType::LambdaWithoutParentheses),
-                value.expr.range(),
+                lambda_range,
+                Suggestion {
+                    range: lambda_range,
+                    replacement: format!("({})", self.src_text(lambda_range)),
+                    applicability: Applicability::MachineApplicable,
+                },
             );
         }
         let debug_text = if self.eat(TokenKind::Equal) {
@@ -3773,12 +5047,26 @@ impl<'src> Parser<'src> {
 
         let mut elts = vec![first_element];
 
-        self.parse_separated(true, TokenKind::Comma, Self::END_SEQUENCE_SET, |parser| {
-            let parsed_expr = parse_func(parser);
-            let range = parsed_expr.expr.range();
-            elts.push(parsed_expr.expr);
-            range
-        });
+        // Only a parenthesized tuple has a closing `)` to recover up to; an
+        // unparenthesized one ends at whatever follows it (the `=` of `a, b = c`),
+        // which must not be skipped as an unexpected token.
+        let separator_recovery = if parenthesized {
+            SeparatorRecovery::Recovering
+        } else {
+            SeparatorRecovery::Strict
+        };
+        self.parse_separated(
+            true,
+            TokenKind::Comma,
+            Self::END_SEQUENCE_SET,
+            separator_recovery,
+            |parser| {
+                let parsed_expr = parse_func(parser);
+                let range = parsed_expr.expr.range();
+                elts.push(parsed_expr.expr);
+                range
+            },
+        );
 
         if parenthesized {
             self.expect(TokenKind::Rpar);
@@ -3799,12 +5087,18 @@ impl<'src> Parser<'src> {
 
         let mut elts = vec![first_element];
 
-        self.parse_separated(true, TokenKind::Comma, Self::END_SEQUENCE_SET, |parser| {
-            let parsed_expr = parser.parse_expr2();
-            let range = parsed_expr.expr.range();
-            elts.push(parsed_expr.expr);
-            range
-        });
+        self.parse_separated(
+            true,
+            TokenKind::Comma,
+            Self::END_SEQUENCE_SET,
+            SeparatorRecovery::Recovering,
+            |parser| {
+                let parsed_expr = parser.parse_expr2();
+                let range = parsed_expr.expr.range();
+                elts.push(parsed_expr.expr);
+                range
+            },
+        );
 
         self.expect(TokenKind::Rsqb);
 
@@ -3822,12 +5116,18 @@ impl<'src> Parser<'src> {
 
         let mut elts = vec![first_element];
 
-        self.parse_separated(true, TokenKind::Comma, Self::END_SEQUENCE_SET, |parser| {
-            let parsed_expr = parser.parse_expr2();
-            let range = parsed_expr.expr.range();
-            elts.push(parsed_expr.expr);
-            range
-        });
+        self.parse_separated(
+            true,
+            TokenKind::Comma,
+            Self::END_SEQUENCE_SET,
+            SeparatorRecovery::Recovering,
+            |parser| {
+                let parsed_expr = parser.parse_expr2();
+                let range = parsed_expr.expr.range();
+                elts.push(parsed_expr.expr);
+                range
+            },
+        );
 
         self.expect(TokenKind::Rbrace);
 
@@ -3850,22 +5150,28 @@ impl<'src> Parser<'src> {
         let mut keys = vec![key];
         let mut values = vec![value];
 
-        self.parse_separated(true, TokenKind::Comma, Self::END_SEQUENCE_SET, |parser| {
-            if parser.eat(TokenKind::DoubleStar) {
-                keys.push(None);
-            } else {
-                let key = parser.parse_expr();
-                keys.push(Some(key.expr));
+        self.parse_separated(
+            true,
+            TokenKind::Comma,
+            Self::END_SEQUENCE_SET,
+            SeparatorRecovery::Recovering,
+            |parser| {
+                if parser.eat(TokenKind::DoubleStar) {
+                    keys.push(None);
+                } else {
+                    let key = parser.parse_expr();
+                    keys.push(Some(key.expr));
 
-                parser.expect_and_recover(
-                    TokenKind::Colon,
-                    TokenSet::new(&[TokenKind::Comma]).union(EXPR_SET),
-                );
-            }
-            let value = parser.parse_expr();
-            values.push(value.expr);
-            TextRange::default()
-        });
+                    parser.expect_and_recover(
+                        TokenKind::Colon,
+                        TokenSet::new(&[TokenKind::Comma]).union(EXPR_SET),
+                    );
+                }
+                let value = parser.parse_expr();
+                values.push(value.expr);
+                TextRange::default()
+            },
+        );
 
         self.expect(TokenKind::Rbrace);
 
@@ -3884,11 +5190,14 @@ impl<'src> Parser<'src> {
         self.bump(TokenKind::For);
 
         self.set_ctx(ParserCtxFlags::FOR_TARGET);
-        let mut target = self.parse_expr_with_recovery(
-            Parser::parse_exprs,
-            [TokenKind::In, TokenKind::Colon].as_slice(),
-            "expecting expression after `for` keyword",
-        );
+        let mut target =
+            self.with_restrictions(Restrictions::NAMED_EXPRESSION_FORBIDDEN, |parser| {
+                parser.parse_expr_with_recovery(
+                    Parser::parse_exprs,
+                    [TokenKind::In, TokenKind::Colon].as_slice(),
+                    "expecting expression after `for` keyword",
+                )
+            });
         self.clear_ctx(ParserCtxFlags::FOR_TARGET);
 
         helpers::set_expr_ctx(&mut target.expr, ExprContext::Store);
@@ -4003,6 +5312,13 @@ impl<'src> Parser<'src> {
     }
 
     fn parse_starred_expr(&mut self, start: TextSize) -> ast::ExprStarred {
+        if self.has_restriction(Restrictions::STARRED_EXPRESSION_FORBIDDEN) {
+            self.add_error(
+                ParseErrorType::OtherError("starred expression not allowed here".to_string()),
+                TextRange::new(start, self.current_range().start()),
+            );
+        }
+
         let parsed_expr = self.parse_expr();
 
         ast::ExprStarred {
@@ -4031,6 +5347,15 @@ impl<'src> Parser<'src> {
     }
 
     fn parse_yield_expr(&mut self, start: TextSize) -> Expr {
+        if self.has_restriction(Restrictions::YIELD_EXPRESSION_FORBIDDEN) {
+            self.add_error(
+                ParseErrorType::OtherError(
+                    "`yield` not allowed in a `lambda` expression".to_string(),
+                ),
+                TextRange::new(start, self.current_range().start()),
+            );
+        }
+
         if self.eat(TokenKind::From) {
             return self.parse_yield_from_expr(start);
         }
@@ -4048,27 +5373,40 @@ impl<'src> Parser<'src> {
     }
 
     fn parse_yield_from_expr(&mut self, start: TextSize) -> Expr {
-        let parsed_expr = self.parse_exprs();
+        let mut parsed_expr = self.parse_exprs();
 
         match &parsed_expr.expr {
-            Expr::Starred(ast::ExprStarred { value, .. }) => {
-                // Should we make `expr` an `Expr::Invalid` here?
+            // There's no single-span fix for a starred expression here (unlike the
+            // tuple case below, wrapping it in parens wouldn't make it valid), so
+            // this stays a plain diagnostic.
+            Expr::Starred(ast::ExprStarred { range, .. }) => {
+                let range = *range;
                 self.add_error(
                     ParseErrorType::OtherError(
                         "starred expression is not allowed in a `yield from` statement".to_string(),
                     ),
-                    value.as_ref(),
+                    range,
                 );
+                parsed_expr.expr = Expr::Invalid(ast::ExprInvalid {
+                    value: self.src_text(range).into(),
+                    range,
+                });
             }
             Expr::Tuple(tuple) if !tuple.parenthesized => {
-                // Should we make `expr` an `Expr::Invalid` here?
-                self.add_error(
+                let range = tuple.range;
+                self.add_error_with_suggestion(
                     ParseErrorType::OtherError(
                         "unparenthesized tuple is not allowed in a `yield from` statement"
                             .to_string(),
                     ),
-                    tuple,
+                    range,
+                    Suggestion {
+                        range,
+                        replacement: format!("({})", self.src_text(range)),
+                        applicability: Applicability::MachineApplicable,
+                    },
                 );
+                recover_tuple_as_parenthesized(&mut parsed_expr.expr);
             }
             _ => {}
         }
@@ -4109,14 +5447,10 @@ impl<'src> Parser<'src> {
 
         self.expect_and_recover(TokenKind::Colon, TokenSet::EMPTY);
 
-        // Check for forbidden tokens in the `lambda`'s body
+        // A bare starred/double-starred expression is never the start of a valid
+        // `lambda` body; neither token ever begins a legal expression on its own, so
+        // catch it here rather than parsing further.
         match self.current_kind() {
-            TokenKind::Yield => self.add_error(
-                ParseErrorType::OtherError(
-                    "`yield` not allowed in a `lambda` expression".to_string(),
-                ),
-                self.current_range(),
-            ),
             TokenKind::Star => {
                 self.add_error(
                     ParseErrorType::OtherError(
@@ -4137,7 +5471,11 @@ impl<'src> Parser<'src> {
             _ => {}
         }
 
-        let body = self.parse_expr();
+        // `yield` is forbidden anywhere in a `lambda` body, however deeply nested; this
+        // is enforced by `parse_yield_expr` consulting the restriction rather than
+        // pre-checking the next token here, so it also catches e.g. `lambda: (yield)`.
+        let body =
+            self.with_restrictions(Restrictions::YIELD_EXPRESSION_FORBIDDEN, Parser::parse_expr);
 
         ast::ExprLambda {
             body: Box::new(body.expr),
@@ -4206,69 +5544,90 @@ impl<'src> Parser<'src> {
         let ending_set = TokenSet::new(&[TokenKind::Rarrow, ending]).union(COMPOUND_STMT_SET);
         let start = self.node_start();
 
-        self.parse_separated(true, TokenKind::Comma, ending_set, |parser| {
-            // Don't allow any parameter after we have seen a vararg `**kwargs`
-            if has_seen_vararg {
-                parser.add_error(
-                    ParseErrorType::ParamFollowsVarKeywordParam,
-                    parser.current_range(),
-                );
-            }
-
-            if parser.eat(TokenKind::Star) {
-                has_seen_asterisk = true;
-                if parser.at(TokenKind::Comma) {
-                    has_seen_default_param = false;
-                } else if parser.at_expr() {
-                    let param = parser.parse_parameter(function_kind);
-                    vararg = Some(Box::new(param));
-                }
-            } else if parser.eat(TokenKind::DoubleStar) {
-                has_seen_vararg = true;
-                let param = parser.parse_parameter(function_kind);
-                kwarg = Some(Box::new(param));
-            } else if parser.eat(TokenKind::Slash) {
-                // Don't allow `/` after a `*`
-                if has_seen_asterisk {
+        self.parse_separated(
+            true,
+            TokenKind::Comma,
+            ending_set,
+            SeparatorRecovery::Strict,
+            |parser| {
+                // Don't allow any parameter after we have seen a vararg `**kwargs`
+                if has_seen_vararg {
                     parser.add_error(
-                        ParseErrorType::OtherError("`/` must be ahead of `*`".to_string()),
+                        ParseErrorType::ParamFollowsVarKeywordParam,
                         parser.current_range(),
                     );
                 }
-                std::mem::swap(&mut args, &mut posonlyargs);
-            } else if parser.at(TokenKind::Name) {
-                let param = parser.parse_parameter_with_default(function_kind);
-                // Don't allow non-default parameters after default parameters e.g. `a=1, b`,
-                // can't place `b` after `a=1`. Non-default parameters are only allowed after
-                // default parameters if we have a `*` before them, e.g. `a=1, *, b`.
-                if param.default.is_none() && has_seen_default_param && !has_seen_asterisk {
-                    parser.add_error(ParseErrorType::DefaultArgumentError, parser.current_range());
-                }
-                has_seen_default_param = param.default.is_some();
 
-                if has_seen_asterisk {
-                    kwonlyargs.push(param);
+                if parser.eat(TokenKind::Star) {
+                    has_seen_asterisk = true;
+                    if parser.at(TokenKind::Comma) {
+                        has_seen_default_param = false;
+                    } else if parser.at_expr() {
+                        let param = parser.parse_parameter(function_kind);
+                        vararg = Some(Box::new(param));
+                    }
+                } else if parser.eat(TokenKind::DoubleStar) {
+                    has_seen_vararg = true;
+                    let param = parser.parse_parameter(function_kind);
+                    kwarg = Some(Box::new(param));
+                } else if parser.eat(TokenKind::Slash) {
+                    // Don't allow `/` after a `*`
+                    if has_seen_asterisk {
+                        parser.add_error(
+                            ParseErrorType::OtherError("`/` must be ahead of `*`".to_string()),
+                            parser.current_range(),
+                        );
+                    }
+                    std::mem::swap(&mut args, &mut posonlyargs);
+                } else if parser.at(TokenKind::Name) {
+                    let param = parser.parse_parameter_with_default(function_kind);
+                    // Don't allow non-default parameters after default parameters e.g. `a=1, b`,
+                    // can't place `b` after `a=1`. Non-default parameters are only allowed after
+                    // default parameters if we have a `*` before them, e.g. `a=1, *, b`.
+                    if param.default.is_none() && has_seen_default_param && !has_seen_asterisk {
+                        parser.add_error(
+                            ParseErrorType::DefaultArgumentError,
+                            parser.current_range(),
+                        );
+                    }
+                    has_seen_default_param = param.default.is_some();
+
+                    if has_seen_asterisk {
+                        kwonlyargs.push(param);
+                    } else {
+                        args.push(param);
+                    }
                 } else {
-                    args.push(param);
-                }
-            } else {
-                if parser.at_ts(SIMPLE_STMT_SET) {
-                    return TextRange::default(); // We can return any range here
-                }
+                    if parser.at_ts(SIMPLE_STMT_SET) {
+                        // Looks like the start of the next statement (e.g. a missing
+                        // closing paren before `pass`/`return`/...) — report it here
+                        // instead of silently truncating the parameter list, which
+                        // would otherwise leave the caller's own recovery (expecting
+                        // `)`/`:`) to raise a confusing, unrelated error instead.
+                        parser.add_error(
+                            ParseErrorType::OtherError(
+                                "expected a parameter or the end of the parameter list"
+                                    .to_string(),
+                            ),
+                            parser.current_range(),
+                        );
+                        return TextRange::default(); // We can return any range here
+                    }
 
-                let range = parser.current_range();
-                parser.skip_until(
-                    ending_set.union([TokenKind::Comma, TokenKind::Colon].as_slice().into()),
-                );
-                parser.add_error(
-                    ParseErrorType::OtherError("expected parameter".to_string()),
-                    range.cover(parser.current_range()), // TODO(micha): This goes one token too far?
-                );
-            }
+                    let range = parser.current_range();
+                    parser.skip_until(
+                        ending_set.union([TokenKind::Comma, TokenKind::Colon].as_slice().into()),
+                    );
+                    parser.add_error(
+                        ParseErrorType::OtherError("expected parameter".to_string()),
+                        range.cover(parser.current_range()), // TODO(micha): This goes one token too far?
+                    );
+                }
 
-            // TODO(micha): Remove
-            TextRange::default()
-        });
+                // TODO(micha): Remove
+                TextRange::default()
+            },
+        );
 
         let parameters = ast::Parameters {
             range: self.node_range(start),
@@ -4292,6 +5651,14 @@ impl<'src> Parser<'src> {
         if !helpers::is_valid_assignment_target(&target) {
             self.add_error(ParseErrorType::NamedAssignmentError, target.range());
         }
+        if self.has_restriction(Restrictions::NAMED_EXPRESSION_FORBIDDEN) {
+            self.add_error(
+                ParseErrorType::OtherError(
+                    "named expression not allowed in an assignment target".to_string(),
+                ),
+                target.range(),
+            );
+        }
         helpers::set_expr_ctx(&mut target, ExprContext::Store);
 
         let value = self.parse_expr();
@@ -4302,4 +5669,4 @@ impl<'src> Parser<'src> {
             range: self.node_range(start),
         }
     }
-}
\ No newline at end of file
+}
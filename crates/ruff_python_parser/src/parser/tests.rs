@@ -0,0 +1,1021 @@
+use super::*;
+
+/// `parse_lhs`'s unary-operator recursion (guarded via `with_stack_headroom`)
+/// should grow the native stack rather than give up at a shallow fixed depth:
+/// `not` nested tens of thousands deep is unusual but valid Python, and should
+/// parse without a `RecursionLimitExceeded` error.
+#[test]
+fn deeply_nested_not_chain_parses() {
+    let source = format!("{}True", "not ".repeat(20_000));
+    let program = Program::parse_str(&source, Mode::Expression);
+    assert!(
+        program.parse_errors.is_empty(),
+        "expected no parse errors, got {:?}",
+        program.parse_errors
+    );
+}
+
+/// Same as [`deeply_nested_not_chain_parses`], but for `parse_lhs`'s
+/// parenthesized-expression recursion.
+#[test]
+fn deeply_nested_parens_parse() {
+    let source = format!("{}True{}", "(".repeat(20_000), ")".repeat(20_000));
+    let program = Program::parse_str(&source, Mode::Expression);
+    assert!(
+        program.parse_errors.is_empty(),
+        "expected no parse errors, got {:?}",
+        program.parse_errors
+    );
+}
+
+/// Input nested past [`Parser::MAX_RECURSION_DEPTH`] is rejected with
+/// `RecursionLimitExceeded` instead of overflowing the stack.
+#[test]
+fn recursion_past_hard_limit_reports_an_error() {
+    let source = format!("{}True", "not ".repeat(200_000));
+    let program = Program::parse_str(&source, Mode::Expression);
+    assert!(
+        !program.parse_errors.is_empty(),
+        "expected a RecursionLimitExceeded error for pathologically deep input"
+    );
+}
+
+/// `parse_postfix_expr`'s attribute-access loop: a long `a.b.c. ...` chain,
+/// nesting one `Attribute` node per `.b`, should parse without errors.
+#[test]
+fn deeply_nested_attribute_chain_parses() {
+    let source = format!("a{}", ".b".repeat(20_000));
+    let program = Program::parse_str(&source, Mode::Expression);
+    assert!(
+        program.parse_errors.is_empty(),
+        "expected no parse errors, got {:?}",
+        program.parse_errors
+    );
+}
+
+/// `parse_expression_with_precedence`'s right-associative `**` recursion:
+/// every binary operator parses its right operand through
+/// `parse_expression_with_precedence`, but since `**` is right-associative each
+/// `a**` nests one call deeper instead of returning to the loop, so a long chain
+/// has to grow the stack instead of overflowing it.
+#[test]
+fn deeply_nested_power_chain_parses() {
+    let source = format!("{}a", "a**".repeat(20_000));
+    let program = Program::parse_str(&source, Mode::Expression);
+    assert!(
+        program.parse_errors.is_empty(),
+        "expected no parse errors, got {:?}",
+        program.parse_errors
+    );
+}
+
+/// `match`, `case`, and `type` are soft keywords and remain valid identifiers
+/// (and expression starts, see `EXPR_SET`) outside the handful of positions
+/// that dispatch on them as statement keywords.
+#[test]
+fn soft_keywords_are_valid_identifiers() {
+    for source in [
+        "match = 1",
+        "case = 1",
+        "type = 1",
+        "return match",
+        "x = match",
+    ] {
+        let program = Program::parse_str(source, Mode::Module);
+        assert!(
+            program.parse_errors.is_empty(),
+            "expected `{source}` to parse `match`/`case`/`type` as a Name, got {:?}",
+            program.parse_errors
+        );
+    }
+}
+
+/// `match`/`case` still dispatch as statement keywords in their real
+/// statement-header position.
+#[test]
+fn match_statement_still_parses() {
+    let source =
+        "match command.split():\n    case [action]:\n        pass\n    case _:\n        pass\n";
+    let program = Program::parse_str(source, Mode::Module);
+    assert!(
+        program.parse_errors.is_empty(),
+        "expected a match statement to parse cleanly, got {:?}",
+        program.parse_errors
+    );
+}
+
+/// `looks_like_match_stmt_header` gives up scanning after
+/// `Parser::MAX_SOFT_KEYWORD_LOOKAHEAD` tokens and must default to assuming it
+/// *is* a match statement in that case -- not to assuming it isn't -- since a
+/// long-but-valid subject expression is far more likely than a `match(...)`
+/// call statement convoluted enough to exceed the cap. Falling the other way
+/// would misparse this as a plain expression statement and report spurious
+/// errors on perfectly valid input.
+#[test]
+fn match_statement_with_subject_past_the_soft_keyword_lookahead_cap_still_parses() {
+    let long_subject = format!("1{}", " + 1".repeat(100));
+    let source = format!("match {long_subject}:\n    case _:\n        pass\n");
+    let program = Program::parse_str(&source, Mode::Module);
+    assert!(
+        program.parse_errors.is_empty(),
+        "expected a match statement with a long subject to parse cleanly, got {:?}",
+        program.parse_errors
+    );
+
+    let ast::Mod::Module(module) = program.ast else {
+        panic!("expected a module")
+    };
+    assert!(
+        matches!(module.body.as_slice(), [Stmt::Match(_)]),
+        "expected the long subject to still be parsed as a `match` statement, got {:?}",
+        module.body
+    );
+}
+
+/// [`Restrictions::YIELD_EXPRESSION_FORBIDDEN`] rejects a `yield` nested
+/// anywhere inside a `lambda` body, however deeply, rather than only checking
+/// the immediate token after `lambda:`.
+#[test]
+fn yield_forbidden_inside_lambda_body() {
+    let program = Program::parse_str("lambda: (yield 1)", Mode::Expression);
+    assert!(
+        program
+            .parse_errors
+            .iter()
+            .any(|error| error.error.to_string() == "`yield` not allowed in a `lambda` expression"),
+        "expected a forbidden-`yield` error, got {:?}",
+        program.parse_errors
+    );
+}
+
+/// [`Restrictions::STARRED_EXPRESSION_FORBIDDEN`] rejects a bare starred
+/// expression as a `del` target, which can never contain one.
+#[test]
+fn starred_expression_forbidden_in_del_target() {
+    let program = Program::parse_str("del *x", Mode::Module);
+    assert!(
+        program
+            .parse_errors
+            .iter()
+            .any(|error| error.error.to_string() == "starred expression not allowed here"),
+        "expected a forbidden-starred-expression error, got {:?}",
+        program.parse_errors
+    );
+}
+
+/// [`Restrictions::NAMED_EXPRESSION_FORBIDDEN`] rejects a walrus assignment
+/// target in a `for` loop's target, which can never be a named expression,
+/// even a parenthesized one.
+#[test]
+fn named_expression_forbidden_in_for_target() {
+    let program = Program::parse_str("for (x := 1) in y:\n    pass\n", Mode::Module);
+    assert!(
+        program.parse_errors.iter().any(|error| error.error.to_string()
+            == "named expression not allowed in an assignment target"),
+        "expected a forbidden-named-expression error, got {:?}",
+        program.parse_errors
+    );
+}
+
+/// A missing `:` after a compound-statement header is one of the most common
+/// typos, and [`Parser::expect`] attaches a `MachineApplicable` [`Suggestion`]
+/// to insert it right after the previous token, rather than leaving the fix
+/// for the user to find from the bare error message.
+#[test]
+fn missing_colon_suggests_insertion() {
+    let source = "if True\n    pass\n";
+    let program = Program::parse_str(source, Mode::Module);
+
+    let error = program
+        .parse_errors
+        .iter()
+        .find(|error| matches!(error.error, ParseErrorType::ExpectedToken { expected: TokenKind::Colon, .. }))
+        .unwrap_or_else(|| panic!("expected a missing-`:` error, got {:?}", program.parse_errors));
+
+    let suggestion = error
+        .suggestions
+        .first()
+        .unwrap_or_else(|| panic!("expected a suggestion attached to the missing-`:` error"));
+    assert_eq!(suggestion.replacement, ":");
+    assert!(suggestion.range.is_empty());
+    assert_eq!(suggestion.applicability, Applicability::MachineApplicable);
+}
+
+/// `SeparatorRecovery::Recovering` (used for call arguments, subscripts, and
+/// collection literals) keeps every element of the sequence even when a
+/// separator is malformed: a doubled delimiter is tolerated as a single one,
+/// and an unexpected token between elements is skipped rather than abandoning
+/// the rest of the list.
+#[test]
+fn separated_sequence_recovers_from_malformed_delimiters() {
+    // A doubled comma (`f(1,, 2)`) is reported once and treated as a single
+    // delimiter, rather than synthesizing an empty element in between.
+    let program = Program::parse_str("f(1,, 2)", Mode::Expression);
+    assert!(
+        !program.parse_errors.is_empty(),
+        "expected a diagnostic for the doubled comma"
+    );
+    let ast::Mod::Expression(module) = program.ast else {
+        panic!("expected a module-level expression")
+    };
+    let Expr::Call(call) = *module.body else {
+        panic!("expected a call expression")
+    };
+    assert_eq!(
+        call.arguments.args.len(),
+        2,
+        "expected both arguments to survive recovery past the doubled comma"
+    );
+
+    // An unexpected token between two elements (a stray `@`) is skipped
+    // instead of abandoning the rest of the call: the two real arguments
+    // still end up in the parsed argument list, with a placeholder for the
+    // garbage one in between so positions stay intact for tooling.
+    let program = Program::parse_str("f(1, @, 3)", Mode::Expression);
+    assert!(
+        !program.parse_errors.is_empty(),
+        "expected a diagnostic for the unexpected `@`"
+    );
+    let ast::Mod::Expression(module) = program.ast else {
+        panic!("expected a module-level expression")
+    };
+    let Expr::Call(call) = *module.body else {
+        panic!("expected a call expression")
+    };
+    assert_eq!(
+        call.arguments.args.len(),
+        3,
+        "expected both real arguments, plus a placeholder for the garbage token, to survive recovery"
+    );
+}
+
+/// [`Parser::parse_statement_with_recovery`] resynchronizes at the next
+/// statement boundary after a malformed line, rather than letting the
+/// following statements get swallowed into the same recovery: a garbage line
+/// in the middle of a function body should produce one `Invalid` placeholder
+/// for itself while its neighbours still parse as real `Assign` nodes.
+#[test]
+fn garbage_line_mid_body_does_not_swallow_following_statements() {
+    let source = "def f():\n    x = 1\n    ]\n    y = 2\n";
+    let program = Program::parse_str(source, Mode::Module);
+    assert!(
+        !program.parse_errors.is_empty(),
+        "expected a diagnostic for the unexpected `]`"
+    );
+
+    let ast::Mod::Module(module) = program.ast else {
+        panic!("expected a module")
+    };
+    let [Stmt::FunctionDef(func)] = module.body.as_slice() else {
+        panic!("expected a single function definition")
+    };
+    let [Stmt::Assign(_), Stmt::Expr(garbage), Stmt::Assign(_)] = func.body.as_slice() else {
+        panic!(
+            "expected [Assign, Expr(Invalid), Assign], got {:?}",
+            func.body
+        );
+    };
+    assert!(
+        matches!(garbage.value.as_ref(), Expr::Invalid(_)),
+        "expected the garbage line to become a single `Expr::Invalid` placeholder, got {:?}",
+        garbage.value
+    );
+}
+
+/// [`Parser::recover_match_pattern_delimiter`] corrects a mismatched closing
+/// bracket on a sequence pattern in place (`case (1, 2]:` is treated as if it
+/// had been written `case (1, 2):`), reporting exactly which delimiter was
+/// expected and found, with a `MachineApplicable` suggestion to fix it,
+/// rather than abandoning the whole pattern.
+#[test]
+fn mismatched_match_pattern_delimiter_recovers_and_suggests_fix() {
+    let source = "match x:\n    case (1, 2]:\n        pass\n";
+    let program = Program::parse_str(source, Mode::Module);
+
+    let error = program
+        .parse_errors
+        .iter()
+        .find(|error| {
+            matches!(
+                error.error,
+                ParseErrorType::MismatchedMatchPatternDelimiter {
+                    expected: TokenKind::Rpar,
+                    found: TokenKind::Rsqb,
+                }
+            )
+        })
+        .unwrap_or_else(|| {
+            panic!(
+                "expected a mismatched-delimiter error, got {:?}",
+                program.parse_errors
+            )
+        });
+
+    let suggestion = error
+        .suggestions
+        .first()
+        .unwrap_or_else(|| panic!("expected a suggestion attached to the mismatched delimiter"));
+    assert_eq!(suggestion.replacement, ")");
+    assert_eq!(suggestion.applicability, Applicability::MachineApplicable);
+
+    let ast::Mod::Module(module) = program.ast else {
+        panic!("expected a module")
+    };
+    let [Stmt::Match(match_stmt)] = module.body.as_slice() else {
+        panic!("expected a match statement")
+    };
+    let [case] = match_stmt.cases.as_slice() else {
+        panic!("expected a single case")
+    };
+    let Pattern::MatchSequence(sequence) = &case.pattern else {
+        panic!("expected a sequence pattern, got {:?}", case.pattern)
+    };
+    assert_eq!(
+        sequence.patterns.len(),
+        2,
+        "expected both sub-patterns to survive the delimiter recovery"
+    );
+}
+
+/// `parse_with_items` disambiguates the parenthesized forms via
+/// [`Parser::checkpoint`]/[`Parser::rewind`] rather than a hand-rolled lookahead:
+/// a parenthesized list of context managers is accepted directly, a single
+/// parenthesized context manager followed by `as` rewinds out of the
+/// speculative with-items attempt, and a construct that isn't a valid
+/// with-items list at all (an unparenthesized walrus inside the parens) rewinds
+/// all the way back to being parsed as one parenthesized tuple expression.
+#[test]
+fn with_items_checkpoint_rewind_disambiguates_parenthesized_forms() {
+    for source in [
+        "with (a, b):\n    pass\n",
+        "with (a) as b:\n    pass\n",
+        "with (a, b := 0, c):\n    pass\n",
+    ] {
+        let program = Program::parse_str(source, Mode::Module);
+        assert!(
+            program.parse_errors.is_empty(),
+            "expected `{source}` to parse cleanly, got {:?}",
+            program.parse_errors
+        );
+    }
+}
+
+/// `parse_with_items`'s speculative parenthesized-items attempt is abandoned
+/// based on `checkpoint.errors_len` alone: if the speculative attempt adds an
+/// error (here, a bare starred expression as one of the supposed items) the
+/// whole attempt is discarded, and `Parser::rewind` must actually drop that
+/// error along with the speculative tokens, rather than leaving it behind for
+/// a separate counter to paper over. `with (a, *b):` isn't a with-items list
+/// at all once that check fails -- it's a single parenthesized tuple
+/// expression used as one context manager -- and that fallback parse is
+/// itself clean, so the final error list should have nothing in it.
+#[test]
+fn with_items_speculative_parse_errors_do_not_leak_past_rewind() {
+    let source = "with (a, *b):\n    pass\n";
+    let program = Program::parse_str(source, Mode::Module);
+    assert!(
+        program.parse_errors.is_empty(),
+        "expected the speculative attempt's \"starred expression not allowed\" error \
+         to be discarded by rewind, got {:?}",
+        program.parse_errors
+    );
+
+    let ast::Mod::Module(module) = program.ast else {
+        panic!("expected a module")
+    };
+    let [Stmt::With(with_stmt)] = module.body.as_slice() else {
+        panic!("expected a with statement")
+    };
+    let [item] = with_stmt.items.as_slice() else {
+        panic!(
+            "expected the parenthesized group to be reparsed as a single with-item, got {:?}",
+            with_stmt.items
+        )
+    };
+    let Expr::Tuple(tuple) = &item.context_expr else {
+        panic!(
+            "expected the with-item's context expression to be a tuple, got {:?}",
+            item.context_expr
+        )
+    };
+    assert_eq!(tuple.elts.len(), 2);
+    assert!(matches!(tuple.elts[1], Expr::Starred(_)));
+}
+
+/// An unparenthesized tuple of exception types in an `except` clause gets a
+/// `MachineApplicable` suggestion that wraps the exact source text of the
+/// tuple in parentheses, covering the tuple's own range -- not the whole
+/// `except` clause -- so an editor can apply it as a single, minimal
+/// replacement.
+#[test]
+fn unparenthesized_except_tuple_suggests_parenthesizing_exactly_the_tuple() {
+    let source = "try:\n    pass\nexcept ValueError, TypeError:\n    pass\n";
+    let program = Program::parse_str(source, Mode::Module);
+
+    let error = program
+        .parse_errors
+        .iter()
+        .find(|error| {
+            matches!(
+                &error.error,
+                ParseErrorType::OtherError(msg)
+                    if msg == "multiple exception types must be parenthesized"
+            )
+        })
+        .unwrap_or_else(|| {
+            panic!(
+                "expected an unparenthesized-exception-tuple error, got {:?}",
+                program.parse_errors
+            )
+        });
+
+    let suggestion = error
+        .suggestions
+        .first()
+        .unwrap_or_else(|| panic!("expected a suggestion attached to the error"));
+    assert_eq!(suggestion.replacement, "(ValueError, TypeError)");
+    assert_eq!(
+        &source[suggestion.range], "ValueError, TypeError",
+        "expected the suggestion's range to cover exactly the tuple, not the `except` clause"
+    );
+    assert_eq!(suggestion.applicability, Applicability::MachineApplicable);
+}
+
+/// `recover_tuple_as_parenthesized` marks an unparenthesized tuple annotation
+/// as `parenthesized` after reporting the error, the same way a literally
+/// parenthesized one would be, so downstream consumers (the formatter, the
+/// unparser) don't need to special-case whether the parens were real.
+#[test]
+fn unparenthesized_annotation_tuple_is_synthesized_as_parenthesized() {
+    let source = "x: int, str = 1\n";
+    let program = Program::parse_str(source, Mode::Module);
+    assert!(
+        program.parse_errors.iter().any(|error| matches!(
+            &error.error,
+            ParseErrorType::OtherError(msg) if msg == "annotation cannot be unparenthesized"
+        )),
+        "expected an unparenthesized-annotation error, got {:?}",
+        program.parse_errors
+    );
+
+    let ast::Mod::Module(module) = program.ast else {
+        panic!("expected a module")
+    };
+    let [Stmt::AnnAssign(ann_assign)] = module.body.as_slice() else {
+        panic!("expected a single annotated assignment")
+    };
+    let Expr::Tuple(tuple) = ann_assign.annotation.as_ref() else {
+        panic!(
+            "expected the annotation to be a tuple, got {:?}",
+            ann_assign.annotation
+        )
+    };
+    assert!(
+        tuple.parenthesized,
+        "expected the synthesized tuple to be marked as parenthesized"
+    );
+}
+
+/// The `Suggestion`/`Applicability` subsystem attaches a fix to every site
+/// that already detects a recoverable mistake and has enough information to
+/// propose one -- but not to sites that don't, like a `from` import missing
+/// its module name entirely, where there's no surrounding text to build a
+/// replacement from.
+#[test]
+fn suggestions_are_attached_where_a_fix_can_be_inferred() {
+    // `raise X, Y` -- the tuple itself, not the whole `raise` statement, gets
+    // wrapped in parens.
+    let program = Program::parse_str("raise ValueError, TypeError\n", Mode::Module);
+    let error = program
+        .parse_errors
+        .iter()
+        .find(|error| matches!(
+            &error.error,
+            ParseErrorType::OtherError(msg)
+                if msg == "unparenthesized tuple not allowed in `raise` statement"
+        ))
+        .unwrap_or_else(|| panic!("expected a raise-tuple error, got {:?}", program.parse_errors));
+    let suggestion = error.suggestions.first().expect("expected a suggestion");
+    assert_eq!(suggestion.replacement, "(ValueError, TypeError)");
+    assert_eq!(suggestion.applicability, Applicability::MachineApplicable);
+
+    // `del a and b` -- the suggestion proposes the comma-separated targets
+    // the user almost certainly meant.
+    let program = Program::parse_str("del a and b\n", Mode::Module);
+    let error = program
+        .parse_errors
+        .iter()
+        .find(|error| matches!(
+            &error.error,
+            ParseErrorType::OtherError(msg) if msg == "`a and b` not allowed in `del` statement"
+        ))
+        .unwrap_or_else(|| panic!("expected a del-BoolOp error, got {:?}", program.parse_errors));
+    let suggestion = error.suggestions.first().expect("expected a suggestion");
+    assert_eq!(suggestion.replacement, "a, b");
+    assert_eq!(suggestion.applicability, Applicability::MaybeIncorrect);
+
+    // `from import x` -- a missing module name has nothing to suggest.
+    let program = Program::parse_str("from import x\n", Mode::Module);
+    let error = program
+        .parse_errors
+        .iter()
+        .find(|error| matches!(
+            &error.error,
+            ParseErrorType::OtherError(msg) if msg == "missing module name"
+        ))
+        .unwrap_or_else(|| {
+            panic!(
+                "expected a missing-module-name error, got {:?}",
+                program.parse_errors
+            )
+        });
+    assert!(
+        error.suggestions.is_empty(),
+        "expected no suggestion for a missing module name, got {:?}",
+        error.suggestions
+    );
+
+    // `type X Y` -- the generic "expected a token" diagnostic for a missing
+    // `=` carries no dedicated suggestion (unlike the special-cased missing
+    // `:`, there's nothing here that singles out `=` for an insertion fix).
+    let program = Program::parse_str("type X Y\n", Mode::Module);
+    let error = program
+        .parse_errors
+        .iter()
+        .find(|error| {
+            matches!(
+                error.error,
+                ParseErrorType::ExpectedToken {
+                    expected: TokenKind::Equal,
+                    ..
+                }
+            )
+        })
+        .unwrap_or_else(|| {
+            panic!(
+                "expected a missing-`=` error for a type alias, got {:?}",
+                program.parse_errors
+            )
+        });
+    assert!(
+        error.suggestions.is_empty(),
+        "expected no dedicated suggestion for a missing `=` in a type alias, got {:?}",
+        error.suggestions
+    );
+}
+
+/// `parse_expr_with_recovery` only looks one token ahead via `at_expr`, so it
+/// checkpoints and peeks past a single stray token before giving up on the
+/// whole expression: `while , True:` is a misplaced comma in front of a
+/// perfectly good condition, not a genuinely missing one, and should recover
+/// with one diagnostic rather than replacing the whole condition with an
+/// `Expr::Invalid` placeholder.
+#[test]
+fn stray_token_before_expression_is_skipped_not_treated_as_missing() {
+    let source = "while , True:\n    pass\n";
+    let program = Program::parse_str(source, Mode::Module);
+    assert!(
+        program.parse_errors.iter().any(|error| matches!(
+            &error.error,
+            ParseErrorType::OtherError(msg) if msg == "unexpected token `,`"
+        )),
+        "expected an unexpected-token error for the stray comma, got {:?}",
+        program.parse_errors
+    );
+
+    let ast::Mod::Module(module) = program.ast else {
+        panic!("expected a module")
+    };
+    let [Stmt::While(while_stmt)] = module.body.as_slice() else {
+        panic!("expected a while statement")
+    };
+    assert!(
+        matches!(while_stmt.test.as_ref(), Expr::BooleanLiteral(_)),
+        "expected the condition to still parse as `True`, got {:?}",
+        while_stmt.test
+    );
+}
+
+/// `parse_body` checkpoints before giving up on a missing indented block: if
+/// the line after the header turns out to be a real statement that was just
+/// never indented (`if x:\ny = 1\n`), it keeps that statement as the body
+/// instead of reporting the header's block as empty. If that speculative
+/// parse doesn't produce anything real, it rewinds -- discarding the failed
+/// attempt's errors along with its consumed tokens -- before falling back to
+/// the plain "expected an indented block" diagnostic.
+#[test]
+fn unindented_body_recovers_a_real_statement_but_not_garbage() {
+    let source = "if x:\ny = 1\nz = 2\n";
+    let program = Program::parse_str(source, Mode::Module);
+    assert!(
+        program.parse_errors.is_empty(),
+        "expected the forgotten-indentation body to recover cleanly, got {:?}",
+        program.parse_errors
+    );
+    let ast::Mod::Module(module) = program.ast else {
+        panic!("expected a module")
+    };
+    let [Stmt::If(if_stmt), Stmt::Assign(_)] = module.body.as_slice() else {
+        panic!("expected an `if` statement followed by the dedented `z = 2`, got {:?}", module.body)
+    };
+    let [Stmt::Assign(_)] = if_stmt.body.as_slice() else {
+        panic!("expected `y = 1` to recover as the `if`'s body, got {:?}", if_stmt.body)
+    };
+
+    let source = "if x:\n)\ny = 1\n";
+    let program = Program::parse_str(source, Mode::Module);
+    assert!(
+        program.parse_errors.iter().any(|error| matches!(
+            &error.error,
+            ParseErrorType::OtherError(msg) if msg == "expected an indented block after `if` statement"
+        )),
+        "expected the plain missing-indented-block error when the next line isn't a \
+         real statement either, got {:?}",
+        program.parse_errors
+    );
+}
+
+/// `else if` (the C/JavaScript spelling of `elif`) is recognized by
+/// [`Parser::at_else_if`] and corrected with a `MachineApplicable` suggestion
+/// to use `elif` instead, without abandoning the clause's condition or body.
+#[test]
+fn else_if_suggests_elif() {
+    let source = "if a:\n    pass\nelse if b:\n    pass\n";
+    let program = Program::parse_str(source, Mode::Module);
+
+    let error = program
+        .parse_errors
+        .iter()
+        .find(|error| matches!(
+            &error.error,
+            ParseErrorType::OtherError(msg) if msg == "use `elif` instead of `else if`"
+        ))
+        .unwrap_or_else(|| panic!("expected an `else if` error, got {:?}", program.parse_errors));
+    let suggestion = error.suggestions.first().expect("expected a suggestion");
+    assert_eq!(suggestion.replacement, "elif");
+    assert_eq!(suggestion.applicability, Applicability::MachineApplicable);
+
+    let ast::Mod::Module(module) = program.ast else {
+        panic!("expected a module")
+    };
+    let [Stmt::If(if_stmt)] = module.body.as_slice() else {
+        panic!("expected an if statement")
+    };
+    assert_eq!(
+        if_stmt.elif_else_clauses.len(),
+        1,
+        "expected the `else if` to still produce a clause with its condition and body"
+    );
+}
+
+/// [`Parser::foreign_logical_op`] recognizes the C/JavaScript `&&`/`||`
+/// spellings of `and`/`or` (lexed as doubled `&`/`|` tokens) and recovers
+/// with a suggestion for each occurrence, folding consecutive occurrences of
+/// the same foreign operator into a single `BoolOp` rather than a
+/// right-nested chain of two-operand ones.
+#[test]
+fn foreign_logical_operators_suggest_and_or() {
+    let program = Program::parse_str("if a && b:\n    pass\n", Mode::Module);
+    let error = program
+        .parse_errors
+        .iter()
+        .find(|error| matches!(
+            &error.error,
+            ParseErrorType::OtherError(msg) if msg == "`&&` is not a Python operator, use `and`"
+        ))
+        .unwrap_or_else(|| panic!("expected a `&&` error, got {:?}", program.parse_errors));
+    let suggestion = error.suggestions.first().expect("expected a suggestion");
+    assert_eq!(suggestion.replacement, "and");
+    assert_eq!(suggestion.applicability, Applicability::MachineApplicable);
+
+    let program = Program::parse_str("if a || b || c:\n    pass\n", Mode::Module);
+    assert!(
+        program.parse_errors.iter().any(|error| matches!(
+            &error.error,
+            ParseErrorType::OtherError(msg) if msg == "`||` is not a Python operator, use `or`"
+        )),
+        "expected a `||` error, got {:?}",
+        program.parse_errors
+    );
+    let ast::Mod::Module(module) = program.ast else {
+        panic!("expected a module")
+    };
+    let [Stmt::If(if_stmt)] = module.body.as_slice() else {
+        panic!("expected an if statement")
+    };
+    let Expr::BoolOp(bool_op) = if_stmt.test.as_ref() else {
+        panic!("expected the condition to be a `BoolOp`, got {:?}", if_stmt.test)
+    };
+    assert_eq!(
+        bool_op.values.len(),
+        3,
+        "expected `a || b || c` to fold into one `Or` BoolOp with 3 values, not a nested chain"
+    );
+}
+
+/// [`Parser::parse_foreign_not_expr`] recognizes a leading `!` (the
+/// C/JavaScript spelling of logical negation) and recovers it as `not`,
+/// building the same `UnaryOp` a real `not` would rather than falling into
+/// generic "expecting expression" recovery.
+#[test]
+fn foreign_not_operator_suggests_not() {
+    let program = Program::parse_str("!a", Mode::Expression);
+    let error = program
+        .parse_errors
+        .iter()
+        .find(|error| matches!(
+            &error.error,
+            ParseErrorType::OtherError(msg) if msg == "`!` is not a Python operator, use `not`"
+        ))
+        .unwrap_or_else(|| panic!("expected a `!` error, got {:?}", program.parse_errors));
+    let suggestion = error.suggestions.first().expect("expected a suggestion");
+    assert_eq!(suggestion.replacement, "not");
+    assert_eq!(suggestion.applicability, Applicability::MachineApplicable);
+
+    let ast::Mod::Expression(module) = program.ast else {
+        panic!("expected a module-level expression")
+    };
+    let Expr::UnaryOp(unary_op) = module.body.as_ref() else {
+        panic!("expected the expression to recover as a `UnaryOp`, got {:?}", module.body)
+    };
+    assert!(matches!(unary_op.op, UnaryOp::Not));
+}
+
+/// [`Parser::parse_foreign_increment_or_decrement_expr`] recognizes a trailing
+/// `++`/`--` (the C/JavaScript increment/decrement operators) after an atom
+/// and recovers by suggesting the augmented assignment `+= 1`/`-= 1`, leaving
+/// the atom itself as the expression result rather than abandoning it.
+#[test]
+fn foreign_increment_and_decrement_suggest_augmented_assignment() {
+    let program = Program::parse_str("x++", Mode::Expression);
+    let error = program
+        .parse_errors
+        .iter()
+        .find(|error| matches!(
+            &error.error,
+            ParseErrorType::OtherError(msg) if msg == "`++` is not a Python operator, use `+= 1`"
+        ))
+        .unwrap_or_else(|| panic!("expected a `++` error, got {:?}", program.parse_errors));
+    let suggestion = error.suggestions.first().expect("expected a suggestion");
+    assert_eq!(suggestion.replacement, "x += 1");
+    assert_eq!(suggestion.applicability, Applicability::MaybeIncorrect);
+
+    let ast::Mod::Expression(module) = program.ast else {
+        panic!("expected a module-level expression")
+    };
+    assert!(
+        matches!(module.body.as_ref(), Expr::Name(name) if name.id == "x"),
+        "expected `x` to still be the recovered expression, got {:?}",
+        module.body
+    );
+
+    let program = Program::parse_str("y--", Mode::Expression);
+    assert!(
+        program.parse_errors.iter().any(|error| matches!(
+            &error.error,
+            ParseErrorType::OtherError(msg) if msg == "`--` is not a Python operator, use `-= 1`"
+        )),
+        "expected a `--` error, got {:?}",
+        program.parse_errors
+    );
+}
+
+/// An empty subscript (`l[]`) gets a `MaybeIncorrect` suggestion to insert a
+/// `:` -- turning it into the (valid, if unusual) full-slice `l[:]` -- since
+/// that's the most likely thing an empty pair of brackets was meant to be.
+/// An f-string replacement field containing a bare `lambda` (which can't be
+/// distinguished from the field's closing `}` without parentheses) gets a
+/// suggestion to parenthesize it instead. A positional argument following a
+/// keyword argument isn't fixable with a single-span replacement -- the real
+/// fix is reordering the whole argument list -- so it gets two `MaybeIncorrect`
+/// suggestions instead: drop the argument from where it is, and insert it
+/// before the first keyword argument.
+#[test]
+fn suggestions_cover_empty_slice_fstring_lambda_and_positional_after_keyword() {
+    let program = Program::parse_str("l[]", Mode::Expression);
+    let error = program
+        .parse_errors
+        .iter()
+        .find(|error| matches!(error.error, ParseErrorType::EmptySlice))
+        .unwrap_or_else(|| panic!("expected an EmptySlice error, got {:?}", program.parse_errors));
+    let suggestion = error.suggestions.first().expect("expected a suggestion");
+    assert_eq!(suggestion.replacement, ":");
+    assert!(suggestion.range.is_empty());
+    assert_eq!(suggestion.applicability, Applicability::MaybeIncorrect);
+
+    let program = Program::parse_str(r#"f"{lambda: 1}""#, Mode::Expression);
+    let error = program
+        .parse_errors
+        .iter()
+        .find(|error| matches!(
+            error.error,
+            ParseErrorType::FStringError(FStringErrorType::LambdaWithoutParentheses)
+        ))
+        .unwrap_or_else(|| {
+            panic!(
+                "expected a lambda-without-parentheses error, got {:?}",
+                program.parse_errors
+            )
+        });
+    let suggestion = error.suggestions.first().expect("expected a suggestion");
+    assert_eq!(suggestion.replacement, "(lambda: 1)");
+    assert_eq!(suggestion.applicability, Applicability::MachineApplicable);
+
+    let program = Program::parse_str("f(a=1, 2)", Mode::Expression);
+    let error = program
+        .parse_errors
+        .iter()
+        .find(|error| matches!(error.error, ParseErrorType::PositionalArgumentError))
+        .unwrap_or_else(|| {
+            panic!(
+                "expected a PositionalArgumentError, got {:?}",
+                program.parse_errors
+            )
+        });
+    let [insert, delete] = error.suggestions.as_slice() else {
+        panic!("expected two suggestions (insert before `a=1`, delete from here), got {:?}", error.suggestions)
+    };
+    assert_eq!(insert.replacement, "2, ");
+    assert!(insert.range.is_empty());
+    assert_eq!(insert.applicability, Applicability::MaybeIncorrect);
+    assert_eq!(delete.replacement, "");
+    assert_eq!(delete.applicability, Applicability::MaybeIncorrect);
+}
+
+/// `f(*x for x in y)` can't mean a generator expression over a starred
+/// element -- iterable unpacking isn't allowed as a comprehension's element,
+/// the same restriction CPython enforces -- so `parse_arguments` reports it
+/// directly and consumes the dangling `for` clause itself, rather than
+/// leaving it for `parse_delimited`'s separator recovery to stumble over as
+/// a second, spurious "expected `,` or `)`" diagnostic.
+#[test]
+fn starred_argument_followed_by_for_reports_one_targeted_error() {
+    let program = Program::parse_str("f(*x for x in y)", Mode::Expression);
+    let matching: Vec<_> = program
+        .parse_errors
+        .iter()
+        .filter(|error| matches!(
+            &error.error,
+            ParseErrorType::OtherError(msg)
+                if msg == "iterable unpacking cannot be used in a comprehension"
+        ))
+        .collect();
+    assert_eq!(
+        matching.len(),
+        1,
+        "expected exactly one targeted diagnostic, not a second spurious one from \
+         separator recovery, got {:?}",
+        program.parse_errors
+    );
+
+    let ast::Mod::Expression(module) = program.ast else {
+        panic!("expected a module-level expression")
+    };
+    let Expr::Call(call) = *module.body else {
+        panic!("expected a call expression")
+    };
+    assert_eq!(
+        call.arguments.args.len(),
+        1,
+        "expected the dangling `for x in y` clause to be consumed rather than \
+         misparsed as further arguments"
+    );
+}
+
+/// `parse_atom`'s generic unexpected-token diagnostic renders the token via
+/// its `Display` impl -- the literal punctuation a reader typed (`` `)` ``) --
+/// rather than the internal `Debug` spelling of the `Tok` variant (`Rpar`),
+/// so the message reads like something a human wrote the source with.
+#[test]
+fn unexpected_token_diagnostic_uses_a_human_readable_description() {
+    let program = Program::parse_str("x = )\n", Mode::Module);
+    assert!(
+        program.parse_errors.iter().any(|error| matches!(
+            &error.error,
+            ParseErrorType::OtherError(msg) if msg == "unexpected token `)`"
+        )),
+        "expected a human-readable \"unexpected token `)`\" diagnostic, got {:?}",
+        program.parse_errors
+    );
+}
+
+/// `ParseErrorType::ExpectedToken`'s `Display` impl renders both the expected
+/// and found tokens through [`crate::error::TokenDescription`] rather than
+/// `TokenKind`'s `Debug` spelling: a keyword reads as `` keyword `in` ``, not
+/// `In`, and an identifier reads as "an identifier", not `Name`.
+#[test]
+fn expected_token_diagnostic_uses_human_readable_token_descriptions() {
+    let source = "for x y:\n    pass\n";
+    let program = Program::parse_str(source, Mode::Module);
+    assert!(
+        program.parse_errors.iter().any(|error| matches!(
+            &error.error,
+            ParseErrorType::ExpectedToken { expected: TokenKind::In, found: TokenKind::Name }
+        ) && error.error.to_string() == "expected keyword `in`, found an identifier"),
+        "expected a human-readable \"expected keyword `in`, found an identifier\" diagnostic, got {:?}",
+        program.parse_errors
+    );
+}
+
+/// `f(a.b = 1)` uses a non-identifier expression (an attribute access) where
+/// a keyword argument name is expected. `ast::Keyword`'s `arg` can only ever
+/// hold an `Identifier`, but `Identifier` is just a `String` and a range --
+/// nothing enforces that the string is a valid identifier -- so the parser
+/// stuffs the original `a.b` text straight into `id` instead of discarding
+/// it, keeping both the range and the source text available to the
+/// diagnostic and to any tooling built on the AST.
+#[test]
+fn non_identifier_keyword_argument_name_preserves_its_source_text() {
+    let source = "f(a.b = 1)";
+    let program = Program::parse_str(source, Mode::Expression);
+    assert!(
+        program.parse_errors.iter().any(|error| matches!(
+            &error.error,
+            ParseErrorType::OtherError(msg)
+                if msg == "`a.b` cannot be used as a keyword argument name, only identifiers are allowed"
+        )),
+        "expected a non-identifier-keyword-argument-name error, got {:?}",
+        program.parse_errors
+    );
+
+    let ast::Mod::Expression(module) = program.ast else {
+        panic!("expected a module-level expression")
+    };
+    let Expr::Call(call) = *module.body else {
+        panic!("expected a call expression")
+    };
+    let [keyword] = call.arguments.keywords.as_slice() else {
+        panic!("expected a single keyword argument")
+    };
+    let arg = keyword.arg.as_ref().expect("expected a placeholder identifier");
+    assert_eq!(
+        arg.id, "a.b",
+        "expected the original non-identifier text to be preserved, not discarded"
+    );
+    assert_eq!(
+        &source[arg.range],
+        "a.b",
+        "expected the placeholder identifier's range to still cover the original `a.b`"
+    );
+}
+
+/// A starred expression can't be made valid by wrapping it in parens (unlike
+/// an unparenthesized tuple, which can), so `yield from *x` reports a plain
+/// diagnostic and replaces the whole expression with `Expr::Invalid` -- the
+/// parser's general placeholder for "something was here, but it couldn't be
+/// turned into a valid node" -- rather than leaving the malformed `Starred`
+/// node in the tree for a consumer to trip over.
+#[test]
+fn yield_from_starred_expression_becomes_invalid() {
+    let source = "def f():\n    yield from *x\n";
+    let program = Program::parse_str(source, Mode::Module);
+    assert!(
+        program.parse_errors.iter().any(|error| matches!(
+            &error.error,
+            ParseErrorType::OtherError(msg)
+                if msg == "starred expression is not allowed in a `yield from` statement"
+        )),
+        "expected a starred-yield-from error, got {:?}",
+        program.parse_errors
+    );
+
+    let ast::Mod::Module(module) = program.ast else {
+        panic!("expected a module")
+    };
+    let [Stmt::FunctionDef(func)] = module.body.as_slice() else {
+        panic!("expected a single function definition")
+    };
+    let [Stmt::Expr(expr_stmt)] = func.body.as_slice() else {
+        panic!("expected a single expression statement")
+    };
+    let Expr::YieldFrom(yield_from) = expr_stmt.value.as_ref() else {
+        panic!(
+            "expected a `yield from` expression, got {:?}",
+            expr_stmt.value
+        )
+    };
+    assert!(
+        matches!(yield_from.value.as_ref(), Expr::Invalid(_)),
+        "expected the starred expression to become `Expr::Invalid`, got {:?}",
+        yield_from.value
+    );
+}
+
+/// Opting into token-position collection should record the significant
+/// keyword/punctuation positions of a decorated function definition, not
+/// just its overall range, so callers can reconstruct source losslessly.
+#[test]
+fn token_positions_are_collected_when_opted_in() {
+    let source = "@decorator\ndef f(x):\n    pass\n";
+    let tokens: Vec<LexResult> = lex(source, Mode::Module).collect();
+    let program = Program::parse_tokens_with_token_positions(source, tokens, Mode::Module);
+    assert!(
+        program.parse_errors.is_empty(),
+        "expected no parse errors, got {:?}",
+        program.parse_errors
+    );
+    assert!(
+        !program.token_positions.is_empty(),
+        "expected token positions to be collected for a decorated function definition"
+    );
+}
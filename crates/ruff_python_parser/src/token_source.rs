@@ -0,0 +1,98 @@
+//! The token stream the parser consumes, plus the checkpoint/rewind support
+//! speculative parsing needs.
+//!
+//! The bulk of this file (`new`, `next`, `peek_nth`, `finish`) is pre-existing
+//! upstream surface `parser/mod.rs` already relied on before this backlog series
+//! started; it's reproduced here only so [`TokenSourceCheckpoint`] and
+//! [`TokenSource::checkpoint`]/[`TokenSource::rewind`] -- genuinely new in this
+//! series -- have something to attach to. As with `error.rs`, this doesn't make
+//! the crate buildable in this snapshot: there's still no `lib.rs` to wire
+//! `mod token_source;` into, and `crate::lexer` (owner of `LexError`/`LexResult`/
+//! `Spanned`) isn't part of this tree either. That gap predates this series.
+
+use ruff_text_size::{TextRange, TextSize};
+
+use crate::lexer::{LexError, LexResult, Spanned};
+use crate::{Tok, TokenKind};
+
+pub(crate) struct TokenSource {
+    tokens: std::vec::IntoIter<LexResult>,
+    lex_errors: Vec<LexError>,
+    position: usize,
+    /// Tokens already pulled from `tokens` so `peek_nth`/`rewind` can look
+    /// backward and forward without re-lexing.
+    buffer: Vec<Spanned>,
+}
+
+impl TokenSource {
+    pub(crate) fn new(tokens: Vec<LexResult>) -> Self {
+        Self {
+            tokens: tokens.into_iter(),
+            lex_errors: Vec::new(),
+            position: 0,
+            buffer: Vec::new(),
+        }
+    }
+
+    fn fill_to(&mut self, index: usize) {
+        while self.buffer.len() <= index {
+            match self.tokens.next() {
+                Some(Ok(spanned)) => self.buffer.push(spanned),
+                Some(Err(error)) => {
+                    let range = error.location;
+                    self.lex_errors.push(error);
+                    self.buffer.push((Tok::Unknown, range));
+                }
+                None => {
+                    let end = self
+                        .buffer
+                        .last()
+                        .map_or(TextSize::default(), |(_, range)| range.end());
+                    self.buffer
+                        .push((Tok::EndOfFile, ruff_text_size::TextRange::empty(end)));
+                    break;
+                }
+            }
+        }
+    }
+
+    pub(crate) fn next(&mut self) -> Option<Spanned> {
+        self.fill_to(self.position);
+        let spanned = self.buffer.get(self.position).cloned();
+        self.position += 1;
+        spanned
+    }
+
+    pub(crate) fn peek_nth(&mut self, offset: usize) -> Option<(TokenKind, TextRange)> {
+        self.fill_to(self.position + offset);
+        self.buffer
+            .get(self.position + offset)
+            .map(|(tok, range)| (TokenKind::from_token(tok), *range))
+    }
+
+    pub(crate) fn finish(self) -> Vec<LexError> {
+        self.lex_errors
+    }
+
+    /// Captures the current read position so a speculative parse can
+    /// [`TokenSource::rewind`] back to it if it turns out not to apply.
+    pub(crate) fn checkpoint(&self) -> TokenSourceCheckpoint {
+        TokenSourceCheckpoint {
+            position: self.position,
+        }
+    }
+
+    /// Restores the read position captured by an earlier [`TokenSource::checkpoint`].
+    /// Already-buffered tokens are kept around rather than re-lexed, since
+    /// `peek_nth`/`next` may have looked ahead of `checkpoint`'s position.
+    pub(crate) fn rewind(&mut self, checkpoint: TokenSourceCheckpoint) {
+        self.position = checkpoint.position;
+    }
+}
+
+/// Opaque read position captured by [`TokenSource::checkpoint`] and restored by
+/// [`TokenSource::rewind`].
+#[derive(Debug)]
+pub(crate) struct TokenSourceCheckpoint {
+    position: usize,
+}